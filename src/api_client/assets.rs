@@ -0,0 +1,321 @@
+use std::{
+	io::ErrorKind,
+	path::{Path, PathBuf},
+	sync::atomic::{AtomicU64, Ordering},
+	time::SystemTime,
+};
+
+use racal::reqwest::ApiClient;
+use reqwest::{Client, header::ACCEPT};
+use sha2::{Digest, Sha256};
+use tokio::io::AsyncReadExt;
+
+use super::ApiError;
+use crate::AssetUrl;
+
+/// Makes the names of temporary files unique within the process
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// A local on-disk cache for Resonite's content addressed assets.
+///
+/// Blobs are stored in a single directory with their hash as the file name.
+/// Once the total size of the blobs exceeds the limit, the least recently used
+/// ones are evicted.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AssetCache {
+	directory: PathBuf,
+	max_size_bytes: u64,
+}
+
+impl AssetCache {
+	/// Removes all the cached blobs
+	///
+	/// # Errors
+	///
+	/// If reading the directory or removing files from it fails
+	pub async fn clear(&self) -> Result<(), ApiError> {
+		for (path, _, _) in self.entries().await? {
+			remove_blob(&path).await?;
+		}
+
+		Ok(())
+	}
+
+	#[must_use]
+	/// The directory that the blobs are stored in
+	pub fn directory(&self) -> &Path { &self.directory }
+
+	/// Lists the blobs in the cache with their sizes and last access times
+	async fn entries(&self) -> Result<Vec<(PathBuf, u64, SystemTime)>, ApiError> {
+		let mut entries = Vec::new();
+		let mut read_dir = match tokio::fs::read_dir(&self.directory).await {
+			Ok(read_dir) => read_dir,
+			Err(e) if e.kind() == ErrorKind::NotFound => {
+				return Ok(entries);
+			}
+			Err(e) => return Err(e.into()),
+		};
+
+		while let Some(entry) = read_dir.next_entry().await? {
+			// Concurrent evictions can remove files while they're being listed
+			let metadata = match entry.metadata().await {
+				Ok(metadata) => metadata,
+				Err(e) if e.kind() == ErrorKind::NotFound => continue,
+				Err(e) => return Err(e.into()),
+			};
+			if !metadata.is_file() || !is_hash(&entry.file_name().to_string_lossy()) {
+				continue;
+			}
+			entries.push((
+				entry.path(),
+				metadata.len(),
+				metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+			));
+		}
+
+		Ok(entries)
+	}
+
+	/// Removes the least recently used blobs until the cache fits the size limit
+	///
+	/// Returns the amount of bytes that were freed.
+	///
+	/// # Errors
+	///
+	/// If reading the directory or removing files from it fails
+	pub async fn evict(&self) -> Result<u64, ApiError> {
+		let mut entries = self.entries().await?;
+		let mut total_size: u64 = entries.iter().map(|(_, size, _)| size).sum();
+		let mut freed = 0;
+
+		// Oldest access times first
+		entries.sort_by_key(|(_, _, accessed)| *accessed);
+		for (path, size, _) in entries {
+			if total_size <= self.max_size_bytes {
+				break;
+			}
+			remove_blob(&path).await?;
+			total_size -= size;
+			freed += size;
+		}
+
+		Ok(freed)
+	}
+
+	/// Gets a blob from the cache, marking it as recently used.
+	///
+	/// # Errors
+	///
+	/// If reading the file fails for other reasons than it not existing
+	pub async fn get(&self, hash: &str) -> Result<Option<Vec<u8>>, ApiError> {
+		let Some(path) = self.path(hash) else {
+			return Ok(None);
+		};
+
+		let mut file = match tokio::fs::File::open(&path).await {
+			Ok(file) => file,
+			Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+			Err(e) => return Err(e.into()),
+		};
+		let mut bytes = Vec::new();
+		file.read_to_end(&mut bytes).await?;
+
+		// The modification time is used for tracking the last usage,
+		// as access times are often disabled on filesystems.
+		// Failing to update it, such as with a read-only cache, only makes the
+		// eviction order less accurate.
+		file.into_std().await.set_modified(SystemTime::now()).ok();
+
+		Ok(Some(bytes))
+	}
+
+	/// Stores a blob in the cache, evicting older ones if needed.
+	///
+	/// Blobs that are larger than the whole cache are silently not stored.
+	///
+	/// # Errors
+	///
+	/// If the hash isn't valid or writing the file fails
+	pub async fn insert(&self, hash: &str, bytes: &[u8]) -> Result<(), ApiError> {
+		let path = self.path(hash).ok_or_else(|| {
+			std::io::Error::new(
+				ErrorKind::InvalidInput,
+				"Asset hash isn't a valid cache key",
+			)
+		})?;
+		if bytes.len() as u64 > self.max_size_bytes {
+			return Ok(());
+		}
+
+		tokio::fs::create_dir_all(&self.directory).await?;
+		// Writing to a temporary file first so that partial writes never end up
+		// being served from the cache.
+		// The name is unique so that concurrent inserts don't overwrite each
+		// other's files.
+		let temp_path = path.with_extension(format!(
+			"{}-{}.tmp",
+			std::process::id(),
+			TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+		));
+		let written = match tokio::fs::write(&temp_path, bytes).await {
+			Ok(()) => tokio::fs::rename(&temp_path, &path).await,
+			Err(e) => Err(e),
+		};
+		if let Err(e) = written {
+			tokio::fs::remove_file(&temp_path).await.ok();
+			return Err(e.into());
+		}
+
+		self.evict().await?;
+
+		Ok(())
+	}
+
+	#[must_use]
+	/// The maximum total size of the blobs in the cache
+	pub const fn max_size_bytes(&self) -> u64 { self.max_size_bytes }
+
+	/// Creates a new cache that's stored in the directory
+	pub fn new(directory: impl Into<PathBuf>, max_size_bytes: u64) -> Self {
+		Self { directory: directory.into(), max_size_bytes }
+	}
+
+	/// The path for a blob, if the hash is valid
+	fn path(&self, hash: &str) -> Option<PathBuf> {
+		is_hash(hash).then(|| self.directory.join(hash.to_ascii_lowercase()))
+	}
+
+	/// The total size of the blobs that are currently cached
+	///
+	/// # Errors
+	///
+	/// If reading the directory fails
+	pub async fn size_bytes(&self) -> Result<u64, ApiError> {
+		Ok(self.entries().await?.iter().map(|(_, size, _)| size).sum())
+	}
+}
+
+/// Downloads Resonite's assets, optionally caching them locally.
+///
+/// `resdb:///` assets have their content verified against the hash in the URL,
+/// and only those verified assets are stored in the cache.
+pub struct AssetFetcher {
+	cache: Option<AssetCache>,
+	http: Client,
+}
+
+impl AssetFetcher {
+	#[must_use]
+	/// The cache that the fetcher uses, if any
+	pub const fn cache(&self) -> Option<&AssetCache> { self.cache.as_ref() }
+
+	/// Gets the asset's content, from the cache if possible.
+	///
+	/// # Errors
+	///
+	/// If the request fails, the content doesn't match the hash,
+	/// or using the cache fails.
+	pub async fn fetch(&self, asset: &AssetUrl) -> Result<Vec<u8>, ApiError> {
+		if let (true, Some(cache)) = (asset.is_resdb(), &self.cache) {
+			if let Some(bytes) = cache.get(asset.id()).await? {
+				return Ok(bytes);
+			}
+		}
+
+		let bytes = self
+			.http
+			.get(asset.resolved_url())
+			.header(ACCEPT, "*/*")
+			.send()
+			.await?
+			.error_for_status()?
+			.bytes()
+			.await?
+			.to_vec();
+
+		if asset.is_resdb() {
+			let received = format!("{:x}", Sha256::digest(&bytes));
+			if !received.eq_ignore_ascii_case(asset.id()) {
+				return Err(ApiError::AssetHashMismatch {
+					expected: asset.id().to_owned(),
+					received,
+				});
+			}
+
+			if let Some(cache) = &self.cache {
+				cache.insert(asset.id(), &bytes).await?;
+			}
+		}
+
+		Ok(bytes)
+	}
+
+	/// Creates a new asset fetcher that uses the API client's HTTP client.
+	///
+	/// Note that any default headers of the API client are sent with the asset
+	/// requests too, so an unauthenticated client should usually be preferred.
+	pub fn new<State>(
+		api_client: &impl ApiClient<State>, cache: Option<AssetCache>,
+	) -> Self {
+		Self { cache, http: api_client.client().clone() }
+	}
+}
+
+/// Removes a blob, which may have already been removed by a concurrent
+/// eviction
+async fn remove_blob(path: &Path) -> std::io::Result<()> {
+	match tokio::fs::remove_file(path).await {
+		Err(e) if e.kind() != ErrorKind::NotFound => Err(e),
+		_ => Ok(()),
+	}
+}
+
+/// If the string looks like a hex encoded hash, which is safe as a file name
+fn is_hash(value: &str) -> bool {
+	!value.is_empty() && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn asset_cache_eviction() {
+	let directory = std::env::temp_dir()
+		.join(format!("resonite-asset-cache-test-{}", std::process::id()));
+	let cache = AssetCache::new(&directory, 8);
+
+	let set_modified = |hash: &str, seconds: u64| {
+		std::fs::File::options()
+			.write(true)
+			.open(directory.join(hash))
+			.unwrap()
+			.set_modified(
+				SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(seconds),
+			)
+			.unwrap();
+	};
+
+	cache.insert("aa", b"12345").await.unwrap();
+	cache.insert("bb", b"678").await.unwrap();
+	assert_eq!(cache.size_bytes().await.unwrap(), 8);
+	set_modified("aa", 1);
+	set_modified("bb", 2);
+
+	// Using the first blob makes the second one the least recently used
+	assert_eq!(cache.get("AA").await.unwrap().unwrap(), b"12345");
+	cache.insert("cc", b"9").await.unwrap();
+	assert!(cache.get("bb").await.unwrap().is_none());
+	assert!(cache.get("cc").await.unwrap().is_some());
+
+	// Too large or invalid hashes aren't stored
+	cache.insert("dd", b"123456789").await.unwrap();
+	assert!(cache.get("dd").await.unwrap().is_none());
+	assert!(matches!(
+		cache.insert("../escape", b"1").await,
+		Err(ApiError::Io(e)) if e.kind() == ErrorKind::InvalidInput
+	));
+
+	cache.clear().await.unwrap();
+	assert_eq!(cache.size_bytes().await.unwrap(), 0);
+	// Blobs that were already removed by someone else are fine
+	remove_blob(&directory.join("cc")).await.unwrap();
+	std::fs::remove_dir(directory).unwrap();
+}
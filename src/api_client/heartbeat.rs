@@ -0,0 +1,134 @@
+use std::time::Duration;
+
+use racal::{FromApiState, reqwest::ApiClient};
+
+use crate::query::{NoAuthentication, NotifyInstanceOnline};
+
+/// Periodically notifies the API that an instance is online on a machine.
+///
+/// Failed notifications are retried with an exponential back off, up to the
+/// maximum delay.
+/// Meant to be ran alongside other tasks of a headless host,
+/// such as broadcasting its status.
+///
+/// # Example usage
+///
+/// ```no_run
+/// # async fn example(
+/// # 	client: resonite::api_client::AuthenticatedResonite,
+/// # 	machine_id: resonite::id::Machine,
+/// # ) {
+/// use std::time::Duration;
+///
+/// use resonite::api_client::InstanceHeartbeat;
+/// let heartbeat = InstanceHeartbeat::new(machine_id, Duration::from_secs(60));
+/// let heartbeat = heartbeat.run(&client, |result| {
+/// 	if let Err(err) = result {
+/// 		eprintln!("Heartbeat failed: {err:?}");
+/// 	}
+/// 	true
+/// });
+/// let status_broadcasting = async {
+/// 	// Publishing the status of the host periodically for example
+/// };
+/// tokio::join!(heartbeat, status_broadcasting);
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InstanceHeartbeat {
+	interval: Duration,
+	machine_id: crate::id::Machine,
+	max_backoff: Duration,
+}
+
+impl InstanceHeartbeat {
+	/// Sends a single notification
+	///
+	/// # Errors
+	///
+	/// If the request fails
+	pub async fn beat<State>(
+		&self, client: &(impl ApiClient<State> + Sync),
+	) -> Result<(), racal::reqwest::ApiError>
+	where
+		NoAuthentication: FromApiState<State>,
+	{
+		client.query(NotifyInstanceOnline(self.machine_id.clone())).await
+	}
+
+	#[must_use]
+	/// How long to wait until the next notification
+	pub fn delay(&self, consecutive_failures: u32) -> Duration {
+		if consecutive_failures == 0 {
+			return self.interval;
+		}
+
+		// Retrying sooner than usual at first, and then backing off
+		let backoff = Duration::from_secs(5)
+			.saturating_mul(2_u32.saturating_pow(consecutive_failures - 1));
+		backoff.min(self.max_backoff)
+	}
+
+	#[must_use]
+	/// How often the notifications are sent when they succeed
+	pub const fn interval(&self) -> Duration { self.interval }
+
+	#[must_use]
+	/// The machine that the notifications are for
+	pub const fn machine_id(&self) -> &crate::id::Machine { &self.machine_id }
+
+	#[must_use]
+	/// The maximum delay between retrying failed notifications
+	pub const fn max_backoff(&self) -> Duration { self.max_backoff }
+
+	#[must_use]
+	/// Creates a new heartbeat, with the maximum back off being the interval
+	pub const fn new(machine_id: crate::id::Machine, interval: Duration) -> Self {
+		Self { interval, machine_id, max_backoff: interval }
+	}
+
+	/// Sends notifications periodically until the callback returns false.
+	///
+	/// The callback is called with the result of every notification.
+	pub async fn run<State>(
+		&self, client: &(impl ApiClient<State> + Sync),
+		mut on_beat: impl FnMut(Result<(), racal::reqwest::ApiError>) -> bool,
+	) where
+		NoAuthentication: FromApiState<State>,
+	{
+		let mut consecutive_failures = 0;
+		loop {
+			let result = self.beat(client).await;
+			if result.is_ok() {
+				consecutive_failures = 0;
+			} else {
+				consecutive_failures += 1;
+			}
+			if !on_beat(result) {
+				return;
+			}
+			tokio::time::sleep(self.delay(consecutive_failures)).await;
+		}
+	}
+
+	#[must_use]
+	/// Sets the maximum delay between retrying failed notifications
+	pub const fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+		self.max_backoff = max_backoff;
+		self
+	}
+}
+
+#[cfg(test)]
+#[test]
+fn heartbeat_backoff() {
+	let machine_id = crate::id::Machine::try_from("M-machine").unwrap();
+	let heartbeat = InstanceHeartbeat::new(machine_id, Duration::from_secs(90))
+		.with_max_backoff(Duration::from_secs(100));
+
+	assert_eq!(heartbeat.delay(0), Duration::from_secs(90));
+	assert_eq!(heartbeat.delay(1), Duration::from_secs(5));
+	assert_eq!(heartbeat.delay(3), Duration::from_secs(20));
+	assert_eq!(heartbeat.delay(7), Duration::from_secs(100));
+	assert_eq!(heartbeat.delay(u32::MAX), Duration::from_secs(100));
+}
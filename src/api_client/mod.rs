@@ -24,28 +24,73 @@
 //!
 //! > Requires the `Authorization` header in addition to the rate limiting.
 
+#[cfg(feature = "http_client")]
+mod assets;
+#[cfg(feature = "http_client")]
+pub use assets::*;
+
+#[cfg(feature = "http_client")]
+mod heartbeat;
+#[cfg(feature = "http_client")]
+pub use heartbeat::*;
+
 #[cfg(feature = "http_client")]
 mod http;
 #[cfg(feature = "http_client")]
 pub use http::*;
 
+#[cfg(feature = "signalr_msgpack")]
+mod msgpack;
+
 #[cfg(feature = "signalr_client")]
 mod signalr;
 #[cfg(feature = "signalr_client")]
 pub use signalr::*;
 
+#[cfg(feature = "http_client")]
+mod statistics;
+#[cfg(feature = "http_client")]
+pub use statistics::*;
+
 /// An error that may happen with an API query
 #[repr(u8)]
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum ApiError {
+	/// A downloaded asset's content didn't match its hash
+	#[cfg(feature = "http_client")]
+	AssetHashMismatch {
+		/// The hash that the asset's URL specified
+		expected: String,
+		/// The hash of the content that was actually received
+		received: String,
+	},
+	/// The `SignalR` connection was lost before a response was received
+	#[cfg(feature = "signalr_client")]
+	Disconnected,
+	/// The `SignalR` hub rejected the protocol handshake
+	#[cfg(feature = "signalr_client")]
+	Handshake(String),
 	/// An error happened with the HTTPS request
 	#[cfg(feature = "http_client")]
 	Http(reqwest::Error),
+	/// The `SignalR` hub responded to an invocation with an error
+	#[cfg(feature = "signalr_client")]
+	Invocation(String),
+	/// An error happened with reading or writing local files
+	#[cfg(feature = "http_client")]
+	Io(std::io::Error),
+	/// A `SignalR` subscriber fell behind, and skipped this many messages
+	#[cfg(feature = "signalr_client")]
+	Lagged(u64),
 	/// An error happened with sending `SignalR` data
 	#[cfg(feature = "signalr_client")]
 	Other(String),
 	/// An error happened with serialization
 	Serde(serde_json::Error),
+	/// A response from the `SignalR` hub wasn't received in time
+	#[cfg(feature = "signalr_client")]
+	Timeout,
 	/// An error happened with the WS connection
 	#[cfg(feature = "signalr_client")]
 	WebSocket(ezsockets::Error),
@@ -60,6 +105,11 @@ impl From<reqwest::Error> for ApiError {
 	fn from(err: reqwest::Error) -> Self { Self::Http(err) }
 }
 
+#[cfg(feature = "http_client")]
+impl From<std::io::Error> for ApiError {
+	fn from(err: std::io::Error) -> Self { Self::Io(err) }
+}
+
 #[cfg(feature = "http_client")]
 impl From<racal::reqwest::ApiError> for ApiError {
 	fn from(err: racal::reqwest::ApiError) -> Self {
@@ -0,0 +1,423 @@
+//! The `MessagePack` hub protocol of `SignalR`.
+//!
+//! The messages are converted from and to the same shape that they have with
+//! the JSON protocol, so that the same models work with both protocols.
+//! The server keys objects by their C# property names, so the first letters of
+//! the keys are lower cased when decoding and upper cased when encoding.
+//!
+//! See <https://github.com/dotnet/aspnetcore/blob/main/src/SignalR/docs/specs/HubProtocol.md#messagepack-msgpack-encoding>
+
+use rmpv::Value as MsgPack;
+use serde_json::{Map, Value as Json};
+use time::{Duration, OffsetDateTime, format_description::well_known::Rfc3339};
+
+use super::ApiError;
+use crate::signalr::Message;
+
+/// A length prefixed ping message
+pub const PING_FRAME: [u8; 3] = [0x02, 0x91, 0x06];
+/// The extension type that `MessagePack` uses for timestamps
+const TIMESTAMP_EXT: i8 = -1;
+
+/// Lower cases the first letter of a C# property name, like JSON keys have
+fn camel_case(key: &str) -> String {
+	let mut chars = key.chars();
+	chars.next().map_or_else(String::new, |first| {
+		first.to_lowercase().chain(chars).collect()
+	})
+}
+
+/// Parses all the length prefixed messages in the bytes
+pub fn decode(mut bytes: &[u8]) -> Vec<Result<Message, String>> {
+	let mut messages = Vec::new();
+	while !bytes.is_empty() {
+		let Some((length, prefix_length)) = read_length(bytes) else {
+			messages.push(Err("Invalid MessagePack frame length".to_owned()));
+			break;
+		};
+		let Some(frame) = bytes.get(prefix_length..prefix_length + length) else {
+			messages.push(Err("Incomplete MessagePack frame".to_owned()));
+			break;
+		};
+		bytes = &bytes[prefix_length + length..];
+		messages.push(decode_message(frame));
+	}
+
+	messages
+}
+
+/// Parses a single message without the length prefix
+fn decode_message(mut frame: &[u8]) -> Result<Message, String> {
+	let MsgPack::Array(items) =
+		rmpv::decode::read_value(&mut frame).map_err(|e| e.to_string())?
+	else {
+		return Err("MessagePack messages should be arrays".to_owned());
+	};
+
+	let mut items = items.into_iter();
+	let message_type = items
+		.next()
+		.and_then(|message_type| message_type.as_u64())
+		.ok_or("MessagePack messages should start with their type")?;
+	let mut next = || items.next().map_or(Ok(Json::Null), to_json);
+
+	let mut fields = Map::new();
+	fields.insert("type".to_owned(), message_type.into());
+	match message_type {
+		// Invocation & stream invocation
+		1 | 4 => {
+			next()?; // Headers
+			fields.insert("invocationId".to_owned(), next()?);
+			fields.insert("target".to_owned(), next()?);
+			fields.insert("arguments".to_owned(), next()?);
+		}
+		// Stream item
+		2 => {
+			next()?;
+			fields.insert("invocationId".to_owned(), next()?);
+			fields.insert("item".to_owned(), next()?);
+		}
+		// Completion
+		3 => {
+			next()?;
+			fields.insert("invocationId".to_owned(), next()?);
+			match next()?.as_u64() {
+				Some(1) => {
+					fields.insert("error".to_owned(), next()?);
+				}
+				Some(3) => {
+					fields.insert("result".to_owned(), next()?);
+				}
+				_ => {}
+			}
+		}
+		// Cancel invocation
+		5 => {
+			next()?;
+			fields.insert("invocationId".to_owned(), next()?);
+		}
+		// Close
+		7 => {
+			fields.insert("error".to_owned(), next()?);
+			let allow_reconnect = next()?.as_bool().unwrap_or_default();
+			fields.insert("allowReconnect".to_owned(), allow_reconnect.into());
+		}
+		_ => {}
+	}
+
+	serde_json::from_value(Json::Object(fields)).map_err(|e| e.to_string())
+}
+
+/// Serializes a message into a length prefixed frame
+pub fn encode(message: &Message) -> Result<Vec<u8>, ApiError> {
+	use serde::ser::Error;
+
+	let Json::Object(mut fields) = serde_json::to_value(message)? else {
+		return Err(serde_json::Error::custom("Messages should be objects").into());
+	};
+	let mut take =
+		|key: &str| fields.remove(key).map_or(MsgPack::Nil, to_msgpack);
+
+	let message_type = take("type");
+	let headers = MsgPack::Map(Vec::new());
+	let items = match message_type.as_u64() {
+		Some(1 | 4) => vec![
+			message_type,
+			headers,
+			take("invocationId"),
+			take("target"),
+			take("arguments"),
+			// Stream IDs
+			MsgPack::Array(Vec::new()),
+		],
+		Some(2) => vec![message_type, headers, take("invocationId"), take("item")],
+		Some(3) => {
+			let invocation_id = take("invocationId");
+			let error = take("error");
+			let result = take("result");
+			if !error.is_nil() {
+				vec![message_type, headers, invocation_id, 1.into(), error]
+			} else if result.is_nil() {
+				vec![message_type, headers, invocation_id, 2.into()]
+			} else {
+				vec![message_type, headers, invocation_id, 3.into(), result]
+			}
+		}
+		Some(5) => vec![message_type, headers, take("invocationId")],
+		Some(7) => {
+			let error = take("error");
+			let allow_reconnect =
+				take("allowReconnect").as_bool().unwrap_or_default();
+			vec![message_type, error, allow_reconnect.into()]
+		}
+		_ => vec![message_type],
+	};
+
+	let mut body = Vec::new();
+	rmpv::encode::write_value(&mut body, &MsgPack::Array(items))
+		.map_err(|e| ApiError::Other(e.to_string()))?;
+
+	let mut frame = Vec::with_capacity(body.len() + 5);
+	write_length(&mut frame, body.len());
+	frame.extend(body);
+	Ok(frame)
+}
+
+/// Upper cases the first letter of a JSON key, like C# property names have
+fn pascal_case(key: &str) -> String {
+	let mut chars = key.chars();
+	chars.next().map_or_else(String::new, |first| {
+		first.to_uppercase().chain(chars).collect()
+	})
+}
+
+/// Reads the variable length prefix of a frame.
+///
+/// Returns the length of the frame, and the length of the prefix.
+fn read_length(bytes: &[u8]) -> Option<(usize, usize)> {
+	let mut length = 0;
+	for (i, byte) in bytes.iter().take(5).enumerate() {
+		length |= usize::from(byte & 0x7f) << (7 * i);
+		if byte & 0x80 == 0 {
+			return Some((length, i + 1));
+		}
+	}
+
+	None
+}
+
+/// Parses the RFC3339 string of a `MessagePack` timestamp
+fn timestamp(data: &[u8]) -> Result<String, String> {
+	fn bytes<const N: usize>(data: &[u8]) -> Result<[u8; N], String> {
+		data.try_into().map_err(|_| "Invalid MessagePack timestamp".to_owned())
+	}
+
+	let (seconds, nanoseconds) = match data.len() {
+		4 => (i64::from(u32::from_be_bytes(bytes(data)?)), 0),
+		8 => {
+			let value = u64::from_be_bytes(bytes(data)?);
+			(
+				i64::try_from(value & 0x3_ffff_ffff).map_err(|e| e.to_string())?,
+				value >> 34,
+			)
+		}
+		12 => (
+			i64::from_be_bytes(bytes(&data[4..])?),
+			u64::from(u32::from_be_bytes(bytes(&data[..4])?)),
+		),
+		_ => return Err("Invalid MessagePack timestamp".to_owned()),
+	};
+
+	let time = OffsetDateTime::from_unix_timestamp(seconds)
+		.map_err(|e| e.to_string())?
+		+ Duration::nanoseconds(
+			i64::try_from(nanoseconds).map_err(|e| e.to_string())?,
+		);
+	time.format(&Rfc3339).map_err(|e| e.to_string())
+}
+
+/// Converts a `MessagePack` value to the JSON one that it'd be with the JSON
+/// protocol
+fn to_json(value: MsgPack) -> Result<Json, String> {
+	Ok(match value {
+		MsgPack::Nil => Json::Null,
+		MsgPack::Boolean(value) => Json::Bool(value),
+		MsgPack::Integer(value) => value
+			.as_u64()
+			.map(Json::from)
+			.or_else(|| value.as_i64().map(Json::from))
+			.unwrap_or_default(),
+		MsgPack::F32(value) => Json::from(f64::from(value)),
+		MsgPack::F64(value) => Json::from(value),
+		MsgPack::String(value) => Json::String(
+			value.into_str().ok_or("MessagePack strings should be valid UTF-8")?,
+		),
+		MsgPack::Binary(value) => Json::from(value),
+		MsgPack::Array(values) => {
+			Json::Array(values.into_iter().map(to_json).collect::<Result<_, _>>()?)
+		}
+		MsgPack::Map(entries) => Json::Object(
+			entries
+				.into_iter()
+				.map(|(key, value)| {
+					let key = match to_json(key)? {
+						Json::String(key) => camel_case(&key),
+						key => key.to_string(),
+					};
+					Ok((key, to_json(value)?))
+				})
+				.collect::<Result<_, String>>()?,
+		),
+		MsgPack::Ext(TIMESTAMP_EXT, data) => Json::String(timestamp(&data)?),
+		MsgPack::Ext(ext_type, _) => {
+			return Err(format!("Unsupported MessagePack extension {ext_type}"));
+		}
+	})
+}
+
+/// Converts a JSON value to a `MessagePack` one
+fn to_msgpack(value: Json) -> MsgPack {
+	match value {
+		Json::Null => MsgPack::Nil,
+		Json::Bool(value) => MsgPack::Boolean(value),
+		Json::Number(value) => value
+			.as_u64()
+			.map(MsgPack::from)
+			.or_else(|| value.as_i64().map(MsgPack::from))
+			.unwrap_or_else(|| MsgPack::F64(value.as_f64().unwrap_or_default())),
+		Json::String(value) => MsgPack::String(value.into()),
+		Json::Array(values) => {
+			MsgPack::Array(values.into_iter().map(to_msgpack).collect())
+		}
+		Json::Object(entries) => MsgPack::Map(
+			entries
+				.into_iter()
+				.map(|(key, value)| {
+					(MsgPack::String(pascal_case(&key).into()), to_msgpack(value))
+				})
+				.collect(),
+		),
+	}
+}
+
+/// Writes the variable length prefix of a frame
+fn write_length(frame: &mut Vec<u8>, mut length: usize) {
+	loop {
+		let mut byte = u8::try_from(length & 0x7f).unwrap_or_default();
+		length >>= 7;
+		if length == 0 {
+			frame.push(byte);
+			return;
+		}
+		byte |= 0x80;
+		frame.push(byte);
+	}
+}
+
+#[cfg(test)]
+#[test]
+fn message_pack_frames() {
+	use crate::signalr::{Completion, Invocation, InvocationData, VariantNumber};
+
+	let ping = Message::Ping { num: VariantNumber };
+	assert_eq!(encode(&ping).unwrap(), PING_FRAME);
+
+	let messages = vec![
+		ping,
+		Message::from(Invocation {
+			// Long enough to need a two byte length prefix
+			data: InvocationData::Debug(("a".repeat(200),)),
+			invocation_id: Some("1".to_owned()),
+		}),
+		Message::Completion {
+			num: VariantNumber,
+			data: Completion {
+				error: None,
+				invocation_id: "1".to_owned(),
+				result: serde_json::json!({ "a": [1, -2, 0.5] }),
+			},
+		},
+		Message::Completion {
+			num: VariantNumber,
+			data: Completion {
+				error: Some("Failed".to_owned()),
+				invocation_id: "2".to_owned(),
+				result: Json::Null,
+			},
+		},
+		Message::Close {
+			num: VariantNumber,
+			allow_reconnect: true,
+			error: Some("Shutting down".to_owned()),
+		},
+	];
+	let frames: Vec<u8> =
+		messages.iter().flat_map(|message| encode(message).unwrap()).collect();
+	let decoded: Vec<Message> =
+		decode(&frames).into_iter().map(Result::unwrap).collect();
+	assert_eq!(decoded, messages);
+
+	assert!(decode(&frames[..frames.len() - 1]).last().unwrap().is_err());
+	assert_eq!(timestamp(&[0, 0, 0, 60]).unwrap(), "1970-01-01T00:01:00Z");
+}
+
+#[cfg(test)]
+#[test]
+fn message_pack_server_keys() {
+	use crate::signalr::{Invocation, InvocationData};
+
+	let timestamp =
+		|seconds: u32| MsgPack::Ext(TIMESTAMP_EXT, seconds.to_be_bytes().to_vec());
+	let session = MsgPack::Map(
+		[
+			("Name", MsgPack::from("<b>Test</b> World")),
+			("SessionId", "S-U-host:test".into()),
+			("NormalizedSessionId", "s-u-host:test".into()),
+			("HostUserId", "U-host".into()),
+			("HostMachineId", "machine".into()),
+			("HostUsername", "Host".into()),
+			("AppVersion", "2024.7.25.1284".into()),
+			("HeadlessHost", true.into()),
+			("SessionURLs", MsgPack::Array(Vec::new())),
+			("SessionUsers", MsgPack::Array(Vec::new())),
+			("JoinedUsers", 1.into()),
+			("ActiveUsers", 1.into()),
+			("TotalJoinedUsers", 1.into()),
+			("TotalActiveUsers", 1.into()),
+			("MaxUsers", 10.into()),
+			("MobileFriendly", false.into()),
+			("SessionBeginTime", timestamp(1_722_204_044)),
+			("LastUpdate", timestamp(1_722_209_147)),
+			// Enums are numbers with the default resolver
+			("AccessLevel", 5.into()),
+		]
+		.into_iter()
+		.map(|(key, value)| (MsgPack::from(key), value))
+		.collect(),
+	);
+	let invocation = MsgPack::Array(vec![
+		1.into(),
+		MsgPack::Map(Vec::new()),
+		MsgPack::Nil,
+		"ReceiveSessionUpdate".into(),
+		MsgPack::Array(vec![session]),
+	]);
+	let mut body = Vec::new();
+	rmpv::encode::write_value(&mut body, &invocation).unwrap();
+	let mut frame = Vec::new();
+	write_length(&mut frame, body.len());
+	frame.extend(body);
+
+	let message = decode(&frame).pop().unwrap().unwrap();
+	let Message::Invocation {
+		data:
+			Invocation {
+				data: InvocationData::ReceiveSessionUpdate((session,)), ..
+			},
+		..
+	} = &message
+	else {
+		panic!("Expected a typed session update, got {message:?}");
+	};
+	assert_eq!(session.host_username, "Host");
+	assert_eq!(session.access_level, crate::model::SessionAccessLevel::Anyone);
+
+	// Encoding restores the C# property names
+	let frame = encode(&message).unwrap();
+	let (length, prefix_length) = read_length(&frame).unwrap();
+	let MsgPack::Array(items) = rmpv::decode::read_value(
+		&mut &frame[prefix_length..prefix_length + length],
+	)
+	.unwrap() else {
+		panic!("MessagePack messages should be arrays");
+	};
+	let arguments = items[4].as_array().unwrap();
+	let keys: Vec<&str> = arguments[0]
+		.as_map()
+		.unwrap()
+		.iter()
+		.filter_map(|(key, _)| key.as_str())
+		.collect();
+	assert!(keys.contains(&"SessionId") && keys.contains(&"HostUsername"));
+	assert!(keys.iter().all(|key| key.starts_with(char::is_uppercase)));
+}
@@ -1,55 +1,604 @@
-use std::time::Duration;
+use std::{
+	collections::{HashMap, HashSet},
+	marker::PhantomData,
+	pin::Pin,
+	sync::{
+		Arc,
+		Mutex,
+		MutexGuard,
+		PoisonError,
+		atomic::{AtomicU64, Ordering},
+	},
+	task::{Context, Poll, ready},
+	time::Duration,
+};
 
 use async_trait::async_trait;
-use serde::Serialize;
-use tokio::{sync::mpsc::UnboundedSender, task::JoinSet, time::timeout};
-use tokio_stream::wrappers::UnboundedReceiverStream;
+use serde::de::DeserializeOwned;
+use tokio::{
+	sync::{
+		broadcast,
+		mpsc::{UnboundedReceiver, UnboundedSender},
+		oneshot,
+		watch,
+	},
+	task::JoinSet,
+	time::{Instant, timeout},
+};
+use tokio_stream::{
+	Stream,
+	wrappers::{BroadcastStream, WatchStream, errors::BroadcastStreamRecvError},
+};
 
 // U+001e
 const SIGNALR_DELIMITER: u8 = b""[0];
 const PROTOCOL_NEGOTIATION: &str = r#"{"protocol":"json","version":1}"#;
+#[cfg(feature = "signalr_msgpack")]
+const MSGPACK_PROTOCOL_NEGOTIATION: &str =
+	r#"{"protocol":"messagepack","version":1}"#;
+const PING_MESSAGE: &str = "{\"type\":6}\u{1e}";
 
 use super::ApiError;
 use crate::query::Authentication;
 
 type ListenMessageResult = Result<crate::signalr::Message, ApiError>;
+type CompletionResult = Result<crate::signalr::Completion, ApiError>;
+/// A received message that's shared with the subscribers, or why parsing it
+/// failed
+type BroadcastMessage = Result<crate::signalr::Message, String>;
+type HandshakeResult = Result<(), String>;
+type StreamItemResult = Result<serde_json::Value, ApiError>;
 
-/// A thread-safe mutex for a stream of receiving messages from the server
-pub type ReceiverContainer = std::sync::Arc<
-	tokio::sync::Mutex<UnboundedReceiverStream<ListenMessageResult>>,
->;
+/// Configuration for the `SignalR` client
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SignalRConfig {
+	/// How long to wait for the connection to be established
+	pub connection_timeout: Duration,
+	/// How many received messages are buffered for each subscriber.
+	///
+	/// Subscribers that fall further behind skip the oldest messages, which
+	/// they are notified of with [`ApiError::Lagged`].
+	pub event_capacity: usize,
+	/// How long to wait for the completion of invocations
+	pub invocation_timeout: Duration,
+	/// How long to wait after sending the last message before sending a ping,
+	/// so that the server knows the client is still connected.
+	///
+	/// Should be smaller than the server's client timeout, which defaults to
+	/// 30 seconds. Zero disables the pings.
+	pub keep_alive_interval: Duration,
+	/// The protocol that the messages are sent with
+	pub protocol: HubProtocol,
+	/// How long to wait for anything from the server before the connection is
+	/// considered lost and reconnected.
+	///
+	/// Should be larger than the server's keep alive interval, which defaults
+	/// to 15 seconds, and than the client's.
+	pub server_timeout: Duration,
+}
+
+impl Default for SignalRConfig {
+	fn default() -> Self {
+		Self {
+			connection_timeout: Duration::from_secs(10),
+			event_capacity: 256,
+			invocation_timeout: Duration::from_secs(30),
+			keep_alive_interval: Duration::from_secs(15),
+			protocol: HubProtocol::default(),
+			server_timeout: Duration::from_secs(30),
+		}
+	}
+}
+
+/// The protocol that the `SignalR` messages are sent with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HubProtocol {
+	/// JSON text, with the messages separated by a record separator character
+	#[default]
+	Json,
+	/// Binary `MessagePack`, with the messages prefixed by their length.
+	///
+	/// Uses less bandwidth and is faster to parse than JSON.
+	/// The messages are converted to the same shape as they'd have with JSON,
+	/// so the same models are used for both.
+	#[cfg(feature = "signalr_msgpack")]
+	MessagePack,
+}
+
+impl HubProtocol {
+	/// Parses the messages that were received in a single `WebSocket` message
+	fn decode(self, bytes: &[u8]) -> Vec<BroadcastMessage> {
+		// SignalR/Resonite sends empty obj sometimes, just ignore it IG
+		const IGNORE_BYTES: &[u8; 2] = b"{}";
+		match self {
+			Self::Json => bytes
+				.split(|b| b == &SIGNALR_DELIMITER)
+				.filter(|v| !v.is_empty() && v != IGNORE_BYTES)
+				.map(|bytes| {
+					//dbg!(String::from_utf8_lossy(bytes));
+					serde_json::from_slice::<crate::signalr::Message>(bytes)
+						.map_err(|e| e.to_string())
+				})
+				.collect(),
+			#[cfg(feature = "signalr_msgpack")]
+			Self::MessagePack => super::msgpack::decode(bytes),
+		}
+	}
+
+	/// Serializes a message into a `SignalR` frame
+	fn encode(
+		self, message: &crate::signalr::Message,
+	) -> Result<Vec<u8>, ApiError> {
+		match self {
+			Self::Json => {
+				let mut data = serde_json::to_vec(message)?;
+				data.push(SIGNALR_DELIMITER);
+				Ok(data)
+			}
+			#[cfg(feature = "signalr_msgpack")]
+			Self::MessagePack => super::msgpack::encode(message),
+		}
+	}
+
+	/// The handshake request that selects the protocol
+	const fn negotiation(self) -> &'static str {
+		match self {
+			Self::Json => PROTOCOL_NEGOTIATION,
+			#[cfg(feature = "signalr_msgpack")]
+			Self::MessagePack => MSGPACK_PROTOCOL_NEGOTIATION,
+		}
+	}
+
+	/// A ping message
+	fn ping(self) -> Vec<u8> {
+		match self {
+			Self::Json => PING_MESSAGE.as_bytes().to_vec(),
+			#[cfg(feature = "signalr_msgpack")]
+			Self::MessagePack => super::msgpack::PING_FRAME.to_vec(),
+		}
+	}
+}
+
+/// The state of the connection to the `SignalR` hub
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ConnectionState {
+	/// The connection is established
+	Connected,
+	/// The first connection is being established
+	Connecting,
+	/// The connection was closed and won't be reconnected
+	Disconnected {
+		/// Why the connection was closed
+		reason: String,
+	},
+	/// The connection was lost and is being reconnected
+	Reconnecting {
+		/// Why the connection was lost
+		reason: String,
+	},
+}
 
 /// A `SignalR` (`WebSocket`) API client
 pub struct ResoniteSignalRClient {
+	config: SignalRConfig,
+	connection_state: Arc<watch::Sender<ConnectionState>>,
+	events: broadcast::Sender<BroadcastMessage>,
 	handle: JoinSet<()>,
 	internal_client: ezsockets::Client<InternalClientExt>,
-	receive: ReceiverContainer,
+	last_sent: LastSent,
+	next_invocation_id: AtomicU64,
+	pending: PendingInvocations,
+	persistent: PersistentInvocations,
+	streams: PendingStreams,
+}
+
+/// A stream of the messages received from the `SignalR` hub.
+///
+/// Every subscription has its own buffer, so slow subscribers don't hold back
+/// others, but instead skip messages that they didn't receive in time.
+pub struct SignalRSubscription {
+	receiver: BroadcastStream<BroadcastMessage>,
+	targets: Option<HashSet<String>>,
+}
+
+impl SignalRSubscription {
+	/// If the message passes the subscription's filter
+	fn accepts(&self, message: &crate::signalr::Message) -> bool {
+		let Some(targets) = &self.targets else {
+			return true;
+		};
+
+		match message {
+			crate::signalr::Message::Invocation { data: invocation, .. } => {
+				targets.contains(invocation.data.as_ref())
+			}
+			_ => false,
+		}
+	}
+}
+
+impl Stream for SignalRSubscription {
+	type Item = ListenMessageResult;
+
+	fn poll_next(
+		mut self: Pin<&mut Self>, cx: &mut Context<'_>,
+	) -> Poll<Option<Self::Item>> {
+		loop {
+			let Some(received) = ready!(Pin::new(&mut self.receiver).poll_next(cx))
+			else {
+				return Poll::Ready(None);
+			};
+
+			return Poll::Ready(Some(match received {
+				Ok(Ok(message)) => {
+					if !self.accepts(&message) {
+						continue;
+					}
+					Ok(message)
+				}
+				Ok(Err(error)) => Err(ApiError::Serde(serde::de::Error::custom(error))),
+				Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+					Err(ApiError::Lagged(skipped))
+				}
+			}));
+		}
+	}
+}
+
+/// When a message was last sent to the server
+#[derive(Clone)]
+struct LastSent(Arc<Mutex<Instant>>);
+
+impl Default for LastSent {
+	fn default() -> Self { Self(Arc::new(Mutex::new(Instant::now()))) }
+}
+
+impl LastSent {
+	fn lock(&self) -> MutexGuard<'_, Instant> {
+		self.0.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+
+	/// Records that a message was just sent
+	fn mark(&self) { *self.lock() = Instant::now(); }
+
+	/// How long until a ping should be sent, if nothing else is sent before it
+	fn until_ping(&self, interval: Duration) -> Duration {
+		interval.saturating_sub(self.lock().elapsed())
+	}
+}
+
+/// Pings the server whenever nothing has been sent for the interval.
+///
+/// The server considers the client disconnected if it doesn't receive
+/// anything for a while, regardless of what the server itself sends.
+async fn keep_alive(
+	interval: Duration, last_sent: LastSent,
+	connection_state: watch::Receiver<ConnectionState>, mut ping: impl FnMut(),
+) {
+	if interval.is_zero() {
+		return;
+	}
+
+	loop {
+		let delay = last_sent.until_ping(interval);
+		if !delay.is_zero() {
+			tokio::time::sleep(delay).await;
+			continue;
+		}
+
+		// Pings aren't allowed before the handshake
+		if *connection_state.borrow() == ConnectionState::Connected {
+			ping();
+		}
+		last_sent.mark();
+	}
+}
+
+/// Invocations that are sent again after reconnecting
+type PersistentInvocations = Arc<Mutex<Vec<crate::signalr::InvocationData>>>;
+
+/// Sends the persistent invocations every time that the hub is ready, or
+/// closes the client if the hub rejects the handshake
+async fn replay_persistent(
+	client: ezsockets::Client<InternalClientExt>,
+	mut handshake_receiver: UnboundedReceiver<HandshakeResult>,
+	last_sent: LastSent, persistent: PersistentInvocations,
+	protocol: HubProtocol,
+) {
+	while let Some(result) = handshake_receiver.recv().await {
+		if let Err(error) = result {
+			client
+				.close(Some(ezsockets::CloseFrame {
+					code: ezsockets::CloseCode::Protocol,
+					reason: format!("Handshake failed: {error}"),
+				}))
+				.ok();
+			return;
+		}
+
+		let invocations =
+			persistent.lock().unwrap_or_else(PoisonError::into_inner).clone();
+		for data in invocations {
+			if let Ok(bytes) =
+				protocol.encode(&crate::signalr::Invocation::from(data).into())
+			{
+				client.binary(bytes).ok();
+				last_sent.mark();
+			}
+		}
+	}
+}
+
+/// Invocations that are waiting for their completions, by invocation ID
+#[derive(Default, Clone)]
+struct PendingInvocations(
+	Arc<Mutex<HashMap<String, oneshot::Sender<CompletionResult>>>>,
+);
+
+impl PendingInvocations {
+	/// Resolves the pending invocation that the completion is for, if any
+	fn complete(&self, completion: &crate::signalr::Completion) {
+		let sender = self.lock().remove(&completion.invocation_id);
+		if let Some(sender) = sender {
+			sender.send(Ok(completion.clone())).ok();
+		}
+	}
+
+	/// Fails all the pending invocations, as their completions won't arrive
+	fn disconnect(&self) {
+		for (_, sender) in self.lock().drain() {
+			sender.send(Err(ApiError::Disconnected)).ok();
+		}
+	}
+
+	fn insert(
+		&self, invocation_id: String,
+	) -> oneshot::Receiver<CompletionResult> {
+		let (sender, receiver) = oneshot::channel();
+		self.lock().insert(invocation_id, sender);
+		receiver
+	}
+
+	fn lock(
+		&self,
+	) -> MutexGuard<'_, HashMap<String, oneshot::Sender<CompletionResult>>> {
+		// The map stays valid even if a panic happened while it was locked
+		self.0.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+
+	fn remove(&self, invocation_id: &str) { self.lock().remove(invocation_id); }
+}
+
+/// Streaming invocations that are waiting for items, by invocation ID
+#[derive(Default, Clone)]
+struct PendingStreams(
+	Arc<Mutex<HashMap<String, UnboundedSender<StreamItemResult>>>>,
+);
+
+impl PendingStreams {
+	/// Ends the stream that the completion is for, if any
+	fn complete(&self, completion: &crate::signalr::Completion) {
+		let sender = self.lock().remove(&completion.invocation_id);
+		if let (Some(sender), Some(error)) = (sender, &completion.error) {
+			sender.send(Err(ApiError::Invocation(error.clone()))).ok();
+		}
+	}
+
+	/// Fails all the streams, as their items won't arrive
+	fn disconnect(&self) {
+		for (_, sender) in self.lock().drain() {
+			sender.send(Err(ApiError::Disconnected)).ok();
+		}
+	}
+
+	fn insert(
+		&self, invocation_id: String,
+	) -> UnboundedReceiver<StreamItemResult> {
+		let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+		self.lock().insert(invocation_id, sender);
+		receiver
+	}
+
+	/// Passes the item on to the stream that it's for, if any
+	fn item(&self, item: &crate::signalr::StreamItem) {
+		let streams = self.lock();
+		if let Some(sender) = streams.get(&item.invocation_id) {
+			sender.send(Ok(item.item.clone())).ok();
+		}
+	}
+
+	fn lock(
+		&self,
+	) -> MutexGuard<'_, HashMap<String, UnboundedSender<StreamItemResult>>> {
+		// The map stays valid even if a panic happened while it was locked
+		self.0.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+
+	/// Returns if the stream was still pending
+	fn remove(&self, invocation_id: &str) -> bool {
+		self.lock().remove(invocation_id).is_some()
+	}
+}
+
+/// The items of a streaming invocation.
+///
+/// Ends when the hub completes the invocation, with an error if the
+/// invocation failed or the connection was lost.
+/// Dropping the stream before that cancels the invocation.
+pub struct InvocationStream<T> {
+	client: ezsockets::Client<InternalClientExt>,
+	invocation_id: String,
+	item_type: PhantomData<fn() -> T>,
+	protocol: HubProtocol,
+	receiver: UnboundedReceiver<StreamItemResult>,
+	streams: PendingStreams,
+}
+
+impl<T> InvocationStream<T> {
+	/// Cancels the invocation, which is the same as dropping the stream
+	pub fn cancel(self) { drop(self); }
+
+	#[must_use]
+	/// The ID of the streaming invocation
+	pub fn invocation_id(&self) -> &str { &self.invocation_id }
+}
+
+impl<T: DeserializeOwned> Stream for InvocationStream<T> {
+	type Item = Result<T, ApiError>;
+
+	fn poll_next(
+		mut self: Pin<&mut Self>, cx: &mut Context<'_>,
+	) -> Poll<Option<Self::Item>> {
+		let item = ready!(self.receiver.poll_recv(cx));
+		Poll::Ready(
+			item
+				.map(|item| item.and_then(|value| Ok(serde_json::from_value(value)?))),
+		)
+	}
+}
+
+impl<T> Drop for InvocationStream<T> {
+	fn drop(&mut self) {
+		// Completed streams have already been removed
+		if !self.streams.remove(&self.invocation_id) {
+			return;
+		}
+
+		let cancel = crate::signalr::CancelInvocation {
+			invocation_id: self.invocation_id.clone(),
+		};
+		if let Ok(bytes) = self.protocol.encode(&cancel.into()) {
+			self.client.binary(bytes).ok();
+		}
+	}
+}
+
+/// Removes the pending invocation if the invoking future is dropped
+struct PendingInvocationGuard<'a> {
+	invocation_id: &'a str,
+	pending: &'a PendingInvocations,
+}
+
+impl Drop for PendingInvocationGuard<'_> {
+	fn drop(&mut self) { self.pending.remove(self.invocation_id); }
 }
 
 struct InternalClientExt {
+	/// If the server allows reconnecting after the connection is closed
+	allow_reconnect: bool,
+	/// If the next received message is the response to the handshake
+	awaiting_handshake: bool,
+	/// Why the server is closing the connection, if it told that
+	close_error: Option<String>,
 	connected_sender: UnboundedSender<bool>,
-	received_sender: UnboundedSender<ListenMessageResult>,
+	connection_state: Arc<watch::Sender<ConnectionState>>,
+	handshake_sender: UnboundedSender<HandshakeResult>,
+	pending: PendingInvocations,
+	protocol: HubProtocol,
+	received_sender: broadcast::Sender<BroadcastMessage>,
+	streams: PendingStreams,
 }
 
 impl InternalClientExt {
+	const fn new(
+		connected_sender: UnboundedSender<bool>,
+		connection_state: Arc<watch::Sender<ConnectionState>>,
+		handshake_sender: UnboundedSender<HandshakeResult>,
+		pending: PendingInvocations, protocol: HubProtocol,
+		received_sender: broadcast::Sender<BroadcastMessage>,
+		streams: PendingStreams,
+	) -> Self {
+		Self {
+			allow_reconnect: true,
+			awaiting_handshake: false,
+			close_error: None,
+			connected_sender,
+			connection_state,
+			handshake_sender,
+			pending,
+			protocol,
+			received_sender,
+			streams,
+		}
+	}
+
+	/// Handles the connection being lost, deciding if it should be reconnected
+	fn connection_lost(
+		&mut self, reason: String,
+	) -> ezsockets::client::ClientCloseMode {
+		self.pending.disconnect();
+		self.streams.disconnect();
+		let reason = self.close_error.take().unwrap_or(reason);
+
+		if self.allow_reconnect {
+			self
+				.connection_state
+				.send_replace(ConnectionState::Reconnecting { reason });
+			ezsockets::client::ClientCloseMode::Reconnect
+		} else {
+			self
+				.connection_state
+				.send_replace(ConnectionState::Disconnected { reason });
+			ezsockets::client::ClientCloseMode::Close
+		}
+	}
+
+	/// Handles the response to the protocol handshake
+	fn handle_handshake(&mut self, bytes: &[u8]) {
+		self.awaiting_handshake = false;
+		let result =
+			serde_json::from_slice::<crate::signalr::HandshakeResponse>(bytes)
+				.map_err(|e| e.to_string())
+				.and_then(crate::signalr::HandshakeResponse::into_result);
+
+		match &result {
+			Ok(()) => {
+				self.connection_state.send_replace(ConnectionState::Connected);
+			}
+			// Trying again won't help with the protocol not being supported
+			Err(error) => {
+				self.allow_reconnect = false;
+				let reason = format!("Handshake failed: {error}");
+				// The client closing itself doesn't call the close callbacks
+				self.connection_lost(reason.clone());
+				// Unless the server happens to close the connection first
+				self.close_error = Some(reason);
+			}
+		}
+		self.handshake_sender.send(result).ok();
+	}
+
 	/// Turns a WS receiving channel to an async streams
-	fn send_ws_msg(&self, bytes: &[u8]) {
-		// SignalR/Resonite sends empty obj sometimes, just ignore it IG
-		const IGNORE_BYTES: &[u8; 2] = b"{}";
-		for bytes in bytes
-			.split(|b| b == &SIGNALR_DELIMITER)
-			.filter(|v| !v.is_empty() && v != IGNORE_BYTES)
-		{
-			//dbg!(String::from_utf8_lossy(bytes));
-			let res: ListenMessageResult =
-				serde_json::from_slice::<crate::signalr::Message>(bytes)
-					.map_err(ApiError::from);
-			match self.received_sender.send(res) {
-				Ok(v) => v,
-				Err(_e) => {
-					// TODO: Error handling
+	fn send_ws_msg(&mut self, mut bytes: &[u8]) {
+		// The handshake response is JSON even with other protocols
+		if self.awaiting_handshake {
+			let end = bytes
+				.iter()
+				.position(|b| b == &SIGNALR_DELIMITER)
+				.unwrap_or(bytes.len());
+			self.handle_handshake(&bytes[..end]);
+			bytes = bytes.get(end + 1..).unwrap_or_default();
+		}
+
+		for res in self.protocol.decode(bytes) {
+			match &res {
+				Ok(crate::signalr::Message::Completion { data, .. }) => {
+					self.pending.complete(data);
+					self.streams.complete(data);
 				}
-			};
+				Ok(crate::signalr::Message::StreamItem { data, .. }) => {
+					self.streams.item(data);
+				}
+				Ok(crate::signalr::Message::Close {
+					allow_reconnect, error, ..
+				}) => {
+					self.allow_reconnect = *allow_reconnect;
+					self.close_error.clone_from(error);
+				}
+				_ => {}
+			}
+			// Fails only if there are no subscribers, which is fine
+			self.received_sender.send(res).ok();
 		}
 	}
 }
@@ -77,35 +626,171 @@ impl ezsockets::ClientExt for InternalClientExt {
 	}
 
 	async fn on_connect(&mut self) -> Result<(), ezsockets::Error> {
+		// Connected only after the handshake succeeds
+		self.allow_reconnect = true;
+		self.awaiting_handshake = true;
+		self.close_error = None;
 		self.connected_sender.send(true).ok();
 
 		Ok(())
 	}
+
+	async fn on_connect_fail(
+		&mut self, error: ezsockets::WSError,
+	) -> Result<ezsockets::client::ClientCloseMode, ezsockets::Error> {
+		self.connection_state.send_modify(|state| {
+			if let ConnectionState::Reconnecting { reason } = state {
+				*reason = error.to_string();
+			}
+		});
+
+		Ok(ezsockets::client::ClientCloseMode::Reconnect)
+	}
+
+	async fn on_close(
+		&mut self, frame: Option<ezsockets::CloseFrame>,
+	) -> Result<ezsockets::client::ClientCloseMode, ezsockets::Error> {
+		Ok(self.connection_lost(frame.map_or_else(
+			|| "Closed by the server".to_owned(),
+			|frame| {
+				format!("Closed by the server: {:?} {}", frame.code, frame.reason)
+			},
+		)))
+	}
+
+	async fn on_disconnect(
+		&mut self,
+	) -> Result<ezsockets::client::ClientCloseMode, ezsockets::Error> {
+		Ok(self.connection_lost("Connection lost".to_owned()))
+	}
 }
 
 impl ResoniteSignalRClient {
-	/// Creates a new `SignalR` client
+	#[must_use]
+	/// The current state of the connection
+	pub fn connection_state(&self) -> ConnectionState {
+		self.connection_state.borrow().clone()
+	}
+
+	#[must_use]
+	/// A stream of the connection's states, starting with the current one.
+	///
+	/// Note that states that change quickly may be skipped, with only the latest
+	/// one being received.
+	pub fn connection_states(&self) -> WatchStream<ConnectionState> {
+		WatchStream::new(self.connection_state.subscribe())
+	}
+
+	/// Invokes a hub method and waits for its result.
+	///
+	/// Any pending invocations fail if the connection is lost.
 	///
 	/// # Errors
 	///
-	/// If creating the client/connection fails
+	/// If sending the invocation fails, the hub returns an error, the
+	/// invocation times out, the connection is lost before the completion is
+	/// received, or the result can't be deserialized.
+	pub async fn invoke<T: DeserializeOwned>(
+		&self, data: crate::signalr::InvocationData,
+	) -> Result<T, ApiError> {
+		let invocation_id = self.next_invocation_id();
+		let receiver = self.pending.insert(invocation_id.clone());
+		let _guard = PendingInvocationGuard {
+			invocation_id: &invocation_id,
+			pending: &self.pending,
+		};
+
+		self.send(
+			&crate::signalr::Invocation {
+				data,
+				invocation_id: Some(invocation_id.clone()),
+			}
+			.into(),
+		)?;
+
+		let completion = timeout(self.config.invocation_timeout, receiver)
+			.await
+			.map_err(|_| ApiError::Timeout)?
+			.map_err(|_| ApiError::Disconnected)??;
+
+		if let Some(error) = completion.error {
+			return Err(ApiError::Invocation(error));
+		}
+
+		Ok(serde_json::from_value(completion.result)?)
+	}
+
+	/// Assigns an ID for a new invocation
+	fn next_invocation_id(&self) -> String {
+		self.next_invocation_id.fetch_add(1, Ordering::Relaxed).to_string()
+	}
+
+	/// Creates a new `SignalR` client with the default configuration
+	///
+	/// # Errors
+	///
+	/// If creating the client/connection fails, or the hub rejects the protocol
+	/// handshake.
 	pub async fn new(
 		user_agent: &str, auth: &Authentication,
+	) -> Result<Self, ApiError> {
+		Self::with_config(user_agent, auth, SignalRConfig::default()).await
+	}
+
+	/// Creates a new `SignalR` client
+	///
+	/// # Errors
+	///
+	/// If creating the client/connection fails, or the hub rejects the protocol
+	/// handshake.
+	pub async fn with_config(
+		user_agent: &str, auth: &Authentication, config: SignalRConfig,
 	) -> Result<Self, ApiError> {
 		let mut ws_config = ezsockets::ClientConfig::new(crate::SIGNALR_HUB_URI);
+		let protocol = config.protocol;
 
 		let (header_name, header_value) = auth.to_header();
 		ws_config = ws_config.header(header_name, header_value);
 		ws_config = ws_config.header("User-Agent", user_agent);
+		// Anything received from the server counts as it being alive, and
+		// SignalR servers send pings every 15 seconds by default.
+		// The heartbeat is based on receiving, so pinging the server is done by
+		// the keep alive task instead, which is based on sending.
+		ws_config = ws_config.socket_config(ezsockets::SocketConfig {
+			heartbeat: config.server_timeout,
+			timeout: config.server_timeout,
+			heartbeat_ping_msg_fn: Arc::new(move |_| {
+				ezsockets::RawMessage::Binary(protocol.ping())
+			}),
+		});
 
-		let (received_sender, received_receiver) =
-			tokio::sync::mpsc::unbounded_channel::<ListenMessageResult>();
+		let (received_sender, _) = broadcast::channel(config.event_capacity);
+		let events = received_sender.clone();
 
 		let (connected_sender, mut connected_receiver) =
 			tokio::sync::mpsc::unbounded_channel::<bool>();
 
+		let connection_state =
+			Arc::new(watch::Sender::new(ConnectionState::Connecting));
+		let pending = PendingInvocations::default();
+		let streams = PendingStreams::default();
+		let internal_connection_state = connection_state.clone();
+		let internal_pending = pending.clone();
+		let internal_streams = streams.clone();
+		let (handshake_sender, mut handshake_receiver) =
+			tokio::sync::mpsc::unbounded_channel::<HandshakeResult>();
 		let (internal_client, future) = ezsockets::connect(
-			|_client| InternalClientExt { connected_sender, received_sender },
+			move |_client| {
+				InternalClientExt::new(
+					connected_sender,
+					internal_connection_state,
+					handshake_sender,
+					internal_pending,
+					protocol,
+					received_sender,
+					internal_streams,
+				)
+			},
 			ws_config,
 		)
 		.await;
@@ -117,29 +802,59 @@ impl ResoniteSignalRClient {
 			future.await.ok();
 		});
 
-		timeout(Duration::from_secs(10), connected_receiver.recv()).await.map_err(
-			|_e| ApiError::Other("Connection establishment timed out".to_string()),
-		)?;
-		internal_client.binary(PROTOCOL_NEGOTIATION).ok();
+		timeout(config.connection_timeout, connected_receiver.recv())
+			.await
+			.map_err(|_e| {
+				ApiError::Other("Connection establishment timed out".to_string())
+			})?;
+		let last_sent = LastSent::default();
+		internal_client.binary(protocol.negotiation()).ok();
+		timeout(config.connection_timeout, handshake_receiver.recv())
+			.await
+			.map_err(|_| ApiError::Timeout)?
+			.ok_or(ApiError::Disconnected)?
+			.map_err(ApiError::Handshake)?;
 
 		// Handle protocol negotiations when reconnecting too
 		let client_clone = internal_client.clone();
+		let last_sent_clone = last_sent.clone();
 		handle.spawn(async move {
-			loop {
-				//dbg!("Awaiting connection");
-				connected_receiver.recv().await;
-
-				client_clone.binary(PROTOCOL_NEGOTIATION).ok();
-				//dbg!("Sent protocol");
+			while connected_receiver.recv().await.is_some() {
+				client_clone.binary(protocol.negotiation()).ok();
+				last_sent_clone.mark();
 			}
 		});
 
+		let client_clone = internal_client.clone();
+		handle.spawn(keep_alive(
+			config.keep_alive_interval,
+			last_sent.clone(),
+			connection_state.subscribe(),
+			move || {
+				client_clone.binary(protocol.ping()).ok();
+			},
+		));
+
+		let persistent = PersistentInvocations::default();
+		handle.spawn(replay_persistent(
+			internal_client.clone(),
+			handshake_receiver,
+			last_sent.clone(),
+			persistent.clone(),
+			protocol,
+		));
+
 		let ws_client = Self {
+			config,
+			connection_state,
+			events,
 			internal_client,
 			handle,
-			receive: std::sync::Arc::new(tokio::sync::Mutex::new(
-				UnboundedReceiverStream::from(received_receiver),
-			)),
+			last_sent,
+			next_invocation_id: AtomicU64::new(0),
+			pending,
+			persistent,
+			streams,
 		};
 
 		Ok(ws_client)
@@ -150,23 +865,547 @@ impl ResoniteSignalRClient {
 	/// # Errors
 	///
 	/// If something with the request failed.
-	pub fn send<T: Serialize>(
+	pub fn send(
 		&self, requestable: &crate::signalr::Message,
 	) -> Result<(), ApiError> {
-		let data = serde_json::to_string(requestable)?;
 		self
 			.internal_client
-			.binary(data)
+			.binary(self.config.protocol.encode(requestable)?)
 			.map_err(|e| ApiError::Other(e.to_string()))?;
+		self.last_sent.mark();
 
 		Ok(())
 	}
 
+	/// Sends an invocation, and sends it again every time after reconnecting.
+	///
+	/// Useful for subscriptions and other state that the hub loses when the
+	/// connection does. Invocations that are already persistent aren't added
+	/// again.
+	///
+	/// # Errors
+	///
+	/// If something with the request failed.
+	pub fn send_persistent(
+		&self, data: crate::signalr::InvocationData,
+	) -> Result<(), ApiError> {
+		{
+			let mut persistent =
+				self.persistent.lock().unwrap_or_else(PoisonError::into_inner);
+			if !persistent.contains(&data) {
+				persistent.push(data.clone());
+			}
+		}
+
+		self.send_invocation(data)
+	}
+
+	/// Stops sending the invocation after reconnecting.
+	///
+	/// Returns if the invocation was persistent.
+	pub fn remove_persistent(
+		&self, data: &crate::signalr::InvocationData,
+	) -> bool {
+		let mut persistent =
+			self.persistent.lock().unwrap_or_else(PoisonError::into_inner);
+		let count = persistent.len();
+		persistent.retain(|persistent_data| persistent_data != data);
+		count != persistent.len()
+	}
+
+	/// Broadcasts the status of the logged in user.
+	///
+	/// # Errors
+	///
+	/// If something with the request failed.
+	pub fn broadcast_status(
+		&self, status: crate::model::UserStatus,
+		target: crate::signalr::BroadcastTarget,
+	) -> Result<(), ApiError> {
+		self.send_invocation(crate::signalr::InvocationData::BroadcastStatus((
+			Box::new(status),
+			target,
+		)))
+	}
+
+	/// Starts the exchange of statuses with the hub.
+	///
+	/// Should be called before broadcasting or requesting statuses.
+	/// Is [persistent](Self::send_persistent), so it's sent again when
+	/// reconnecting.
+	///
+	/// # Errors
+	///
+	/// If something with the request failed.
+	pub fn initialize_status(&self) -> Result<(), ApiError> {
+		self.send_persistent(crate::signalr::InvocationData::InitializeStatus(
+			crate::signalr::NoArguments(),
+		))
+	}
+
+	/// Marks messages from a sender as read.
+	///
+	/// The sender gets notified with
+	/// [`MessagesRead`](crate::signalr::InvocationData::MessagesRead).
+	///
+	/// # Errors
+	///
+	/// If something with the request failed.
+	pub fn mark_messages_read(
+		&self, batch: crate::signalr::MarkReadBatch,
+	) -> Result<(), ApiError> {
+		self.send_invocation(crate::signalr::InvocationData::MarkMessagesRead((
+			batch,
+		)))
+	}
+
+	/// Requests the status of an user, or all contacts if `None`.
+	///
+	/// The statuses are received as
+	/// [`ReceiveStatusUpdate`](crate::signalr::InvocationData::ReceiveStatusUpdate)
+	/// events.
+	///
+	/// # Errors
+	///
+	/// If something with the request failed.
+	pub fn request_status(
+		&self, user_id: Option<crate::id::User>, invisible: bool,
+	) -> Result<(), ApiError> {
+		self.send_invocation(crate::signalr::InvocationData::RequestStatus((
+			user_id, invisible,
+		)))
+	}
+
+	/// Sends a message to another user.
+	///
+	/// The hub confirms the delivery with
+	/// [`MessageSent`](crate::signalr::InvocationData::MessageSent).
+	///
+	/// # Errors
+	///
+	/// If something with the request failed.
+	pub fn send_message(
+		&self, message: crate::model::Message,
+	) -> Result<(), ApiError> {
+		self.send_invocation(crate::signalr::InvocationData::SendMessage((
+			Box::new(message),
+		)))
+	}
+
+	/// Starts a streaming invocation, whose results are received as a stream.
+	///
+	/// Any streams fail if the connection is lost.
+	///
+	/// # Errors
+	///
+	/// If sending the invocation fails. Errors with the items are returned
+	/// from the stream.
+	pub fn stream<T: DeserializeOwned>(
+		&self, data: crate::signalr::InvocationData,
+	) -> Result<InvocationStream<T>, ApiError> {
+		let invocation_id = self.next_invocation_id();
+		let receiver = self.streams.insert(invocation_id.clone());
+
+		let invocation = crate::signalr::StreamInvocation {
+			data,
+			invocation_id: invocation_id.clone(),
+		};
+		if let Err(e) = self.send(&invocation.into()) {
+			self.streams.remove(&invocation_id);
+			return Err(e);
+		}
+
+		Ok(InvocationStream {
+			client: self.internal_client.clone(),
+			invocation_id,
+			item_type: PhantomData,
+			protocol: self.config.protocol,
+			receiver,
+			streams: self.streams.clone(),
+		})
+	}
+
+	/// Sends an invocation that doesn't expect a result
+	fn send_invocation(
+		&self, data: crate::signalr::InvocationData,
+	) -> Result<(), ApiError> {
+		self.send(&crate::signalr::Invocation::from(data).into())
+	}
+
+	#[must_use]
+	/// Subscribes to all the messages sent by the server,
+	/// starting from the next one.
+	pub fn listen(&self) -> SignalRSubscription {
+		SignalRSubscription {
+			receiver: BroadcastStream::new(self.events.subscribe()),
+			targets: None,
+		}
+	}
+
 	#[must_use]
-	/// Gets the events sent by the server
-	pub fn listen(&self) -> ReceiverContainer { self.receive.clone() }
+	/// Subscribes to the invocations sent by the server with specific targets,
+	/// such as `ReceiveSessionUpdate`.
+	///
+	/// The targets are the names of the
+	/// [`InvocationData`](crate::signalr::InvocationData) variants.
+	/// Other kinds of messages, such as pings, are skipped.
+	pub fn listen_to(
+		&self, targets: impl IntoIterator<Item = impl Into<String>>,
+	) -> SignalRSubscription {
+		SignalRSubscription {
+			receiver: BroadcastStream::new(self.events.subscribe()),
+			targets: Some(targets.into_iter().map(Into::into).collect()),
+		}
+	}
 }
 
 impl Drop for ResoniteSignalRClient {
-	fn drop(&mut self) { self.handle.abort_all(); }
+	fn drop(&mut self) {
+		self.handle.abort_all();
+		self.pending.disconnect();
+		self.streams.disconnect();
+		self.connection_state.send_replace(ConnectionState::Disconnected {
+			reason: "The client was dropped".to_owned(),
+		});
+	}
+}
+
+#[cfg(test)]
+#[test]
+fn completion_correlation() {
+	let (connected_sender, _connected_receiver) =
+		tokio::sync::mpsc::unbounded_channel();
+	let (received_sender, mut received_receiver) = broadcast::channel(16);
+	let pending = PendingInvocations::default();
+	let mut internal_client = InternalClientExt::new(
+		connected_sender,
+		Arc::new(watch::Sender::new(ConnectionState::Connected)),
+		tokio::sync::mpsc::unbounded_channel().0,
+		pending.clone(),
+		HubProtocol::Json,
+		received_sender,
+		PendingStreams::default(),
+	);
+
+	let mut first = pending.insert("1".to_owned());
+	let mut second = pending.insert("2".to_owned());
+	internal_client
+		.send_ws_msg(b"{\"type\":3,\"invocationId\":\"1\",\"result\":true}\x1e");
+
+	let completion = first.try_recv().unwrap().unwrap();
+	assert_eq!(completion.result, serde_json::Value::Bool(true));
+	assert!(second.try_recv().is_err());
+	// Completions are still passed on to the listeners
+	assert!(received_receiver.try_recv().unwrap().is_ok());
+
+	pending.disconnect();
+	assert!(matches!(second.try_recv(), Ok(Err(ApiError::Disconnected))));
+}
+
+#[cfg(test)]
+#[test]
+fn ping_message() {
+	let (connected_sender, _connected_receiver) =
+		tokio::sync::mpsc::unbounded_channel();
+	let (received_sender, mut received_receiver) = broadcast::channel(16);
+	let mut internal_client = InternalClientExt::new(
+		connected_sender,
+		Arc::new(watch::Sender::new(ConnectionState::Connected)),
+		tokio::sync::mpsc::unbounded_channel().0,
+		PendingInvocations::default(),
+		HubProtocol::Json,
+		received_sender,
+		PendingStreams::default(),
+	);
+
+	internal_client.send_ws_msg(PING_MESSAGE.as_bytes());
+	assert!(matches!(
+		received_receiver.try_recv().unwrap().unwrap(),
+		crate::signalr::Message::Ping { .. }
+	));
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn connection_state_changes() {
+	use ezsockets::ClientExt;
+
+	let (connected_sender, mut connected_receiver) =
+		tokio::sync::mpsc::unbounded_channel();
+	let (received_sender, _received_receiver) = broadcast::channel(16);
+	let connection_state =
+		Arc::new(watch::Sender::new(ConnectionState::Connecting));
+	let pending = PendingInvocations::default();
+	let (handshake_sender, mut handshake_receiver) =
+		tokio::sync::mpsc::unbounded_channel();
+	let mut internal_client = InternalClientExt::new(
+		connected_sender,
+		connection_state.clone(),
+		handshake_sender,
+		pending.clone(),
+		HubProtocol::Json,
+		received_sender,
+		PendingStreams::default(),
+	);
+
+	internal_client.on_connect().await.unwrap();
+	assert_eq!(connected_receiver.try_recv(), Ok(true));
+	assert_eq!(*connection_state.borrow(), ConnectionState::Connecting);
+	internal_client.send_ws_msg(b"{}\x1e");
+	assert_eq!(handshake_receiver.try_recv(), Ok(Ok(())));
+	assert_eq!(*connection_state.borrow(), ConnectionState::Connected);
+
+	let mut invocation = pending.insert("1".to_owned());
+	let close_mode = internal_client.on_disconnect().await.unwrap();
+	assert!(matches!(close_mode, ezsockets::client::ClientCloseMode::Reconnect));
+	assert!(matches!(
+		*connection_state.borrow(),
+		ConnectionState::Reconnecting { .. }
+	));
+	assert!(matches!(invocation.try_recv(), Ok(Err(ApiError::Disconnected))));
+
+	internal_client.on_connect().await.unwrap();
+	internal_client.send_ws_msg(b"{}\x1e");
+	assert_eq!(*connection_state.borrow(), ConnectionState::Connected);
+
+	// The server can forbid reconnecting when closing the connection
+	internal_client.send_ws_msg(
+		b"{\"type\":7,\"error\":\"Shutting down\",\"allowReconnect\":false}\x1e",
+	);
+	let close_mode = internal_client.on_close(None).await.unwrap();
+	assert!(matches!(close_mode, ezsockets::client::ClientCloseMode::Close));
+	assert_eq!(
+		*connection_state.borrow(),
+		ConnectionState::Disconnected { reason: "Shutting down".to_owned() }
+	);
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn handshake_error() {
+	use ezsockets::ClientExt;
+
+	let (handshake_sender, mut handshake_receiver) =
+		tokio::sync::mpsc::unbounded_channel();
+	let (received_sender, mut received_receiver) = broadcast::channel(16);
+	let connection_state =
+		Arc::new(watch::Sender::new(ConnectionState::Connecting));
+	let pending = PendingInvocations::default();
+	let mut internal_client = InternalClientExt::new(
+		tokio::sync::mpsc::unbounded_channel().0,
+		connection_state.clone(),
+		handshake_sender,
+		pending.clone(),
+		HubProtocol::Json,
+		received_sender,
+		PendingStreams::default(),
+	);
+
+	internal_client.on_connect().await.unwrap();
+	internal_client.send_ws_msg(b"{}\x1e");
+	assert_eq!(handshake_receiver.try_recv(), Ok(Ok(())));
+
+	// Failing the handshake after reconnecting disconnects right away, as the
+	// client closing itself doesn't call the close callbacks
+	let mut invocation = pending.insert("1".to_owned());
+	internal_client.on_connect().await.unwrap();
+	internal_client.send_ws_msg(b"{\"error\":\"Protocol not supported\"}\x1e");
+	assert!(handshake_receiver.try_recv().unwrap().is_err());
+	// The handshake response isn't passed on to the listeners
+	assert!(received_receiver.try_recv().is_err());
+	assert_eq!(
+		*connection_state.borrow(),
+		ConnectionState::Disconnected {
+			reason: "Handshake failed: Protocol not supported".to_owned()
+		}
+	);
+	assert!(matches!(invocation.try_recv(), Ok(Err(ApiError::Disconnected))));
+
+	let close_mode = internal_client.on_disconnect().await.unwrap();
+	assert!(matches!(close_mode, ezsockets::client::ClientCloseMode::Close));
+	assert!(matches!(
+		*connection_state.borrow(),
+		ConnectionState::Disconnected { .. }
+	));
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn subscription_fan_out() {
+	use tokio_stream::StreamExt;
+
+	let (received_sender, _) = broadcast::channel(2);
+	let subscribe = |targets: Option<&[&str]>| SignalRSubscription {
+		receiver: BroadcastStream::new(received_sender.subscribe()),
+		targets: targets
+			.map(|targets| targets.iter().map(ToString::to_string).collect()),
+	};
+	let mut all = subscribe(None);
+	let mut debug = subscribe(Some(&["Debug"]));
+	let mut slow = subscribe(None);
+
+	let mut internal_client = InternalClientExt::new(
+		tokio::sync::mpsc::unbounded_channel().0,
+		Arc::new(watch::Sender::new(ConnectionState::Connected)),
+		tokio::sync::mpsc::unbounded_channel().0,
+		PendingInvocations::default(),
+		HubProtocol::Json,
+		received_sender.clone(),
+		PendingStreams::default(),
+	);
+	internal_client.send_ws_msg(PING_MESSAGE.as_bytes());
+	internal_client.send_ws_msg(
+		b"{\"type\":1,\"target\":\"Debug\",\"arguments\":[\"test\"]}\x1e",
+	);
+
+	assert!(matches!(
+		all.next().await.unwrap().unwrap(),
+		crate::signalr::Message::Ping { .. }
+	));
+	assert!(matches!(
+		all.next().await.unwrap().unwrap(),
+		crate::signalr::Message::Invocation { .. }
+	));
+	assert!(matches!(
+		debug.next().await.unwrap().unwrap(),
+		crate::signalr::Message::Invocation { .. }
+	));
+
+	// Slow subscribers are told how many messages they missed
+	internal_client.send_ws_msg(PING_MESSAGE.as_bytes());
+	assert!(matches!(slow.next().await, Some(Err(ApiError::Lagged(1)))));
+	assert!(slow.next().await.unwrap().is_ok());
+}
+
+#[cfg(test)]
+#[test]
+fn stream_correlation() {
+	let (received_sender, _received_receiver) = broadcast::channel(16);
+	let streams = PendingStreams::default();
+	let mut internal_client = InternalClientExt::new(
+		tokio::sync::mpsc::unbounded_channel().0,
+		Arc::new(watch::Sender::new(ConnectionState::Connected)),
+		tokio::sync::mpsc::unbounded_channel().0,
+		PendingInvocations::default(),
+		HubProtocol::Json,
+		received_sender,
+		streams.clone(),
+	);
+
+	let mut first = streams.insert("1".to_owned());
+	let mut second = streams.insert("2".to_owned());
+	internal_client.send_ws_msg(
+		b"{\"type\":2,\"invocationId\":\"1\",\"item\":1}\x1e\
+		{\"type\":2,\"invocationId\":\"1\",\"item\":2}\x1e\
+		{\"type\":3,\"invocationId\":\"1\"}\x1e\
+		{\"type\":3,\"invocationId\":\"2\",\"error\":\"Failed\"}\x1e",
+	);
+
+	assert_eq!(first.try_recv().unwrap().unwrap(), serde_json::json!(1));
+	assert_eq!(first.try_recv().unwrap().unwrap(), serde_json::json!(2));
+	// Completed streams end
+	assert!(matches!(
+		first.try_recv(),
+		Err(tokio::sync::mpsc::error::TryRecvError::Disconnected)
+	));
+	assert!(matches!(second.try_recv(), Ok(Err(ApiError::Invocation(_)))));
+	assert!(!streams.remove("1"));
+
+	let mut third = streams.insert("3".to_owned());
+	streams.disconnect();
+	assert!(matches!(third.try_recv(), Ok(Err(ApiError::Disconnected))));
+}
+
+#[cfg(test)]
+#[tokio::test(start_paused = true)]
+async fn keep_alive_schedule() {
+	let interval = Duration::from_secs(15);
+	let start = Instant::now();
+	let last_sent = LastSent::default();
+	let connection_state = watch::Sender::new(ConnectionState::Connected);
+	let (ping_sender, mut pings) = tokio::sync::mpsc::unbounded_channel();
+	tokio::spawn(keep_alive(
+		interval,
+		last_sent.clone(),
+		connection_state.subscribe(),
+		move || {
+			ping_sender.send(Instant::now() - start).ok();
+		},
+	));
+
+	tokio::time::sleep(Duration::from_secs(16)).await;
+	assert_eq!(pings.try_recv(), Ok(Duration::from_secs(15)));
+	assert!(pings.try_recv().is_err());
+
+	// Sending anything postpones the next ping
+	tokio::time::sleep(Duration::from_secs(10)).await;
+	last_sent.mark();
+	tokio::time::sleep(Duration::from_secs(10)).await;
+	assert!(pings.try_recv().is_err());
+	tokio::time::sleep(Duration::from_secs(6)).await;
+	assert_eq!(pings.try_recv(), Ok(Duration::from_secs(41)));
+
+	// Only connected clients are pinged
+	connection_state.send_replace(ConnectionState::Reconnecting {
+		reason: "Connection lost".to_owned(),
+	});
+	tokio::time::sleep(Duration::from_secs(30)).await;
+	assert!(pings.try_recv().is_err());
+	connection_state.send_replace(ConnectionState::Connected);
+	tokio::time::sleep(Duration::from_secs(15)).await;
+	assert_eq!(pings.try_recv(), Ok(Duration::from_secs(86)));
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn drop_disconnects_streams() {
+	use tokio_stream::StreamExt;
+
+	let connection_state =
+		Arc::new(watch::Sender::new(ConnectionState::Connected));
+	let pending = PendingInvocations::default();
+	let streams = PendingStreams::default();
+	let (events, _) = broadcast::channel(16);
+	let internal_connection_state = connection_state.clone();
+	let internal_pending = pending.clone();
+	let internal_streams = streams.clone();
+	let internal_events = events.clone();
+	// Never connects, which doesn't matter as messages are just discarded
+	let (internal_client, _future) = ezsockets::connect(
+		move |_client| {
+			InternalClientExt::new(
+				tokio::sync::mpsc::unbounded_channel().0,
+				internal_connection_state,
+				tokio::sync::mpsc::unbounded_channel().0,
+				internal_pending,
+				HubProtocol::Json,
+				internal_events,
+				internal_streams,
+			)
+		},
+		ezsockets::ClientConfig::new("ws://127.0.0.1:9"),
+	)
+	.await;
+	let client = ResoniteSignalRClient {
+		config: SignalRConfig::default(),
+		connection_state,
+		events,
+		handle: JoinSet::new(),
+		internal_client,
+		last_sent: LastSent::default(),
+		next_invocation_id: AtomicU64::new(0),
+		pending,
+		persistent: PersistentInvocations::default(),
+		streams,
+	};
+
+	let mut stream = client
+		.stream::<u32>(crate::signalr::InvocationData::Debug(("test".to_owned(),)))
+		.unwrap();
+	drop(client);
+	assert!(matches!(
+		timeout(Duration::from_secs(1), stream.next()).await,
+		Ok(Some(Err(ApiError::Disconnected)))
+	));
+	assert!(stream.next().await.is_none());
 }
@@ -0,0 +1,124 @@
+use std::time::Duration;
+
+use racal::{FromApiState, reqwest::ApiClient};
+
+use crate::{
+	model::{CloudStatistics, OnlineStatistics, StatisticsHistory},
+	query::NoAuthentication,
+};
+
+/// Collects statistics snapshots into time series.
+///
+/// # Example usage
+///
+/// ```no_run
+/// # async fn example() {
+/// use resonite::api_client::{StatisticsPoller, UnauthenticatedResonite};
+/// let client =
+/// 	UnauthenticatedResonite::new("Dashboard/1.0.0".to_owned()).unwrap();
+/// let mut poller = StatisticsPoller::new(1440);
+/// poller
+/// 	.run(&client, std::time::Duration::from_secs(60), |poller, result| {
+/// 		if let Err(err) = result {
+/// 			eprintln!("Polling statistics failed: {err:?}");
+/// 		}
+/// 		let changes = poller.online().user_count_changes();
+/// 		println!("User count changes: {changes:?}");
+/// 		true
+/// 	})
+/// 	.await;
+/// # }
+/// ```
+pub struct StatisticsPoller {
+	cloud: StatisticsHistory<CloudStatistics>,
+	online: StatisticsHistory<OnlineStatistics>,
+}
+
+impl StatisticsPoller {
+	#[must_use]
+	/// The collected cloud statistics
+	pub const fn cloud(&self) -> &StatisticsHistory<CloudStatistics> {
+		&self.cloud
+	}
+
+	#[must_use]
+	/// Continues collecting to existing histories, such as ones loaded from disk
+	pub const fn from_histories(
+		cloud: StatisticsHistory<CloudStatistics>,
+		online: StatisticsHistory<OnlineStatistics>,
+	) -> Self {
+		Self { cloud, online }
+	}
+
+	#[must_use]
+	/// Gets the collected cloud and online statistics histories
+	pub fn into_histories(
+		self,
+	) -> (StatisticsHistory<CloudStatistics>, StatisticsHistory<OnlineStatistics>)
+	{
+		(self.cloud, self.online)
+	}
+
+	#[must_use]
+	/// Creates a poller that keeps up to the capacity of snapshots of each kind
+	pub const fn new(capacity: usize) -> Self {
+		Self {
+			cloud: StatisticsHistory::new(capacity),
+			online: StatisticsHistory::new(capacity),
+		}
+	}
+
+	#[must_use]
+	/// The collected online statistics
+	pub const fn online(&self) -> &StatisticsHistory<OnlineStatistics> {
+		&self.online
+	}
+
+	/// Fetches the current statistics and adds them to the histories.
+	///
+	/// Both of the statistics are fetched even if the other one fails.
+	///
+	/// # Errors
+	///
+	/// If fetching either of the statistics fails, with the first error
+	pub async fn poll<State>(
+		&mut self, client: &(impl ApiClient<State> + Sync),
+	) -> Result<(), racal::reqwest::ApiError>
+	where
+		NoAuthentication: FromApiState<State>,
+	{
+		let online = client.query(crate::query::OnlineStatistics).await;
+		let cloud = client.query(crate::query::CloudStatistics).await;
+
+		let online = online.map(|online| {
+			self.online.push(online);
+		});
+		let cloud = cloud.map(|cloud| {
+			self.cloud.push(cloud);
+		});
+
+		online.and(cloud)
+	}
+
+	/// Polls the statistics periodically until the callback returns false.
+	///
+	/// The callback is called after every poll with its result, and can be
+	/// used for handling errors or persisting the histories for example.
+	pub async fn run<State>(
+		&mut self, client: &(impl ApiClient<State> + Sync), interval: Duration,
+		mut on_poll: impl FnMut(&Self, Result<(), racal::reqwest::ApiError>) -> bool,
+	) where
+		NoAuthentication: FromApiState<State>,
+	{
+		let mut interval = tokio::time::interval(interval);
+		interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+		loop {
+			interval.tick().await;
+			let result = self.poll(client).await;
+			if !on_poll(self, result) {
+				return;
+			}
+		}
+	}
+}
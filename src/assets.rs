@@ -18,6 +18,7 @@ pub struct AssetUrl {
 
 impl AssetUrl {
 	const URL_PREFIX: &'static str = "https://assets.resonite.com/assets/";
+	const VARIANT_URL_PREFIX: &'static str = "https://variants.resonite.com/";
 
 	fn from_url(
 		url: impl AsRef<str>, is_resdb: bool,
@@ -85,6 +86,12 @@ impl AssetUrl {
 	/// Gets the file's name without the extension
 	pub fn id(&self) -> &str { &self.id }
 
+	#[must_use]
+	/// If the asset is stored in Resonite's own content addressed storage.
+	///
+	/// Meaning that it was a `resdb:///` URL and the ID is the content's hash.
+	pub const fn is_resdb(&self) -> bool { self.is_resdb }
+
 	#[must_use]
 	/// The `https://` URL needed to retrieve the asset.
 	pub fn resolved_url(&self) -> String {
@@ -93,6 +100,18 @@ impl AssetUrl {
 			_ => self.url_prefix.clone() + &self.id,
 		}
 	}
+
+	#[must_use]
+	/// The `https://` URL needed to retrieve a computed variant of the asset.
+	///
+	/// Only `resdb:///` assets have variants, so others return `None`.
+	pub fn variant_url(
+		&self, variant: &crate::model::AssetVariant,
+	) -> Option<String> {
+		self.is_resdb.then(|| {
+			Self::VARIANT_URL_PREFIX.to_owned() + &self.id + "-" + &variant.identifier
+		})
+	}
 }
 
 impl Display for AssetUrl {
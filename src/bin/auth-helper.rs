@@ -173,10 +173,12 @@ fn main() {
 			.expect("Writing user session bin to work");
 	}
 
-	println!(
-		"Dumping config files not implemented, received: {:?}",
-		user_session.config_files
-	);
+	let config_file_paths = user_session
+		.materialize_config_files("local/config")
+		.expect("Writing config files to work");
+	for path in config_file_paths {
+		println!("Wrote config file {}", path.display());
+	}
 }
 
 #[cfg(feature = "http_client")]
@@ -43,7 +43,7 @@ fn main() {
 
 	{
 		println!("Querying cloud stats");
-		let d = rt.block_on(client.query(query::Sessions)).unwrap();
+		let d = rt.block_on(client.query(query::Sessions::default())).unwrap();
 
 		std::fs::write("local/cloud-stats.json", serde_json::to_vec(&d).unwrap())
 			.unwrap();
@@ -67,7 +67,7 @@ fn main() {
 
 	{
 		println!("Querying all sessions");
-		let d = rt.block_on(client.query(query::Sessions)).unwrap();
+		let d = rt.block_on(client.query(query::Sessions::default())).unwrap();
 
 		std::fs::write("local/all-sessions.json", serde_json::to_vec(&d).unwrap())
 			.unwrap();
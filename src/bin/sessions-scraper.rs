@@ -58,7 +58,7 @@ fn main() {
 
 		println!("Querying all sessions to {filename}");
 
-		let d = match rt.block_on(client.query(query::Sessions)) {
+		let d = match rt.block_on(client.query(query::Sessions::default())) {
 			Ok(d) => d,
 			Err(e) => {
 				eprintln!("Failed querying sessions; {e}");
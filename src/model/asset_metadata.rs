@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[serde_with::serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+/// Details about an asset that the cloud has computed
+pub struct AssetMetadata {
+	/// The hash of the asset
+	pub asset_identifier: String,
+	#[serde_as(deserialize_as = "serde_with::DefaultOnNull")]
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	/// The height of the asset, for bitmaps
+	pub height: Option<u32>,
+	#[serde_as(deserialize_as = "serde_with::DefaultOnNull")]
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	/// The width of the asset, for bitmaps
+	pub width: Option<u32>,
+}
@@ -0,0 +1,225 @@
+use std::fmt::Display;
+
+#[repr(u8)]
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// The kind of an asset variant
+pub enum AssetVariantKind {
+	/// A 2D texture
+	Bitmap2D,
+	/// A 3D texture
+	Bitmap3D,
+	/// A cubemap texture
+	BitmapCube,
+	/// A 3D model
+	Mesh,
+	/// Not yet supported kind of a variant
+	Other(String),
+	/// A compiled shader
+	Shader,
+}
+
+impl From<&str> for AssetVariantKind {
+	fn from(value: &str) -> Self {
+		match value {
+			"Bitmap2D" => Self::Bitmap2D,
+			"Bitmap3D" => Self::Bitmap3D,
+			"BitmapCube" => Self::BitmapCube,
+			"Mesh" => Self::Mesh,
+			"Shader" => Self::Shader,
+			other => Self::Other(other.to_owned()),
+		}
+	}
+}
+
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// A computed variant of an asset, such as a smaller texture.
+///
+/// Parsed from the variant's identifier, which is in the form of
+/// `{kind}_{width}x{height}_{format}_{flags...}` for bitmaps for example.
+pub struct AssetVariant {
+	/// The format of the variant's data, such as `BC3` or `WebP`
+	pub format: Option<String>,
+	/// The height of the variant, for bitmaps
+	pub height: Option<u32>,
+	/// The original identifier of the variant
+	pub identifier: String,
+	/// What kind of an asset the variant is
+	pub kind: AssetVariantKind,
+	/// Any other parts of the identifier
+	pub parameters: Vec<String>,
+	/// The width of the variant, for bitmaps
+	pub width: Option<u32>,
+}
+
+impl AssetVariant {
+	#[must_use]
+	/// The larger one of the variant's dimensions, if it has any
+	pub fn max_dimension(&self) -> Option<u32> {
+		match (self.width, self.height) {
+			(Some(width), Some(height)) => Some(width.max(height)),
+			(width, height) => width.or(height),
+		}
+	}
+}
+
+impl From<&str> for AssetVariant {
+	fn from(identifier: &str) -> Self {
+		let mut parts = identifier.split('_');
+		let kind = AssetVariantKind::from(parts.next().unwrap_or_default());
+
+		let mut format = None;
+		let mut height = None;
+		let mut width = None;
+		let mut parameters = Vec::new();
+		for part in parts {
+			if width.is_none() {
+				if let Some((w, h)) = part.split_once('x') {
+					if let (Ok(w), Ok(h)) = (w.parse(), h.parse()) {
+						width = Some(w);
+						height = Some(h);
+						continue;
+					}
+				}
+			}
+			if format.is_none() && width.is_some() {
+				format = Some(part.to_owned());
+			} else {
+				parameters.push(part.to_owned());
+			}
+		}
+
+		Self {
+			format,
+			height,
+			identifier: identifier.to_owned(),
+			kind,
+			parameters,
+			width,
+		}
+	}
+}
+
+impl Display for AssetVariant {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.identifier)
+	}
+}
+
+impl<'de> serde::de::Deserialize<'de> for AssetVariant {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::de::Deserializer<'de>,
+	{
+		let identifier = String::deserialize(deserializer)?;
+		Ok(Self::from(identifier.as_str()))
+	}
+}
+
+impl serde::ser::Serialize for AssetVariant {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::ser::Serializer,
+	{
+		serializer.serialize_str(&self.identifier)
+	}
+}
+
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// What kind of a variant is wanted, used for picking the best one.
+///
+/// # Example usage
+///
+/// ```
+/// use resonite::model::{AssetVariant, AssetVariantKind, AssetVariantTarget};
+/// let variants: Vec<AssetVariant> = vec![
+/// 	"Bitmap2D_1024x1024_WebP".into(),
+/// 	"Bitmap2D_256x128_WebP".into(),
+/// 	"Bitmap2D_128x64_WebP".into(),
+/// 	"Bitmap2D_256x256_BC3".into(),
+/// ];
+/// let thumbnail = AssetVariantTarget {
+/// 	format: Some("webp".to_owned()),
+/// 	kind: AssetVariantKind::Bitmap2D,
+/// 	max_dimension: Some(256),
+/// };
+/// let best = thumbnail.select(&variants).unwrap();
+/// assert_eq!(best.identifier, "Bitmap2D_256x128_WebP");
+/// ```
+pub struct AssetVariantTarget {
+	/// The wanted format, compared case insensitively
+	pub format: Option<String>,
+	/// The wanted kind of the variant
+	pub kind: AssetVariantKind,
+	/// The maximum allowed width or height
+	pub max_dimension: Option<u32>,
+}
+
+impl AssetVariantTarget {
+	#[must_use]
+	/// If the variant fulfills the requirements
+	pub fn matches(&self, variant: &AssetVariant) -> bool {
+		if variant.kind != self.kind {
+			return false;
+		}
+
+		if let Some(format) = &self.format {
+			if !variant.format.as_ref().is_some_and(|variant_format| {
+				variant_format.eq_ignore_ascii_case(format)
+			}) {
+				return false;
+			}
+		}
+
+		self.max_dimension.is_none_or(|max_dimension| {
+			variant
+				.max_dimension()
+				.is_some_and(|dimension| dimension <= max_dimension)
+		})
+	}
+
+	#[must_use]
+	/// Picks the largest variant that matches the requirements
+	pub fn select<'a>(
+		&self, variants: impl IntoIterator<Item = &'a AssetVariant>,
+	) -> Option<&'a AssetVariant> {
+		variants.into_iter().filter(|variant| self.matches(variant)).max_by_key(
+			|variant| {
+				u64::from(variant.width.unwrap_or_default())
+					* u64::from(variant.height.unwrap_or_default())
+			},
+		)
+	}
+}
+
+#[cfg(test)]
+#[test]
+fn asset_variant() {
+	let variant: AssetVariant =
+		serde_json::from_str("\"Bitmap2D_512x256_BC3_Crunched\"").unwrap();
+	assert_eq!(variant.kind, AssetVariantKind::Bitmap2D);
+	assert_eq!(variant.width, Some(512));
+	assert_eq!(variant.height, Some(256));
+	assert_eq!(variant.format.as_deref(), Some("BC3"));
+	assert_eq!(variant.parameters, vec!["Crunched".to_owned()]);
+	assert_eq!(
+		serde_json::to_string(&variant).unwrap(),
+		"\"Bitmap2D_512x256_BC3_Crunched\""
+	);
+
+	let variant = AssetVariant::from("MeshThing_Simplified");
+	assert_eq!(variant.kind, AssetVariantKind::Other("MeshThing".to_owned()));
+	assert_eq!(variant.max_dimension(), None);
+	assert_eq!(variant.parameters, vec!["Simplified".to_owned()]);
+}
@@ -0,0 +1,353 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// A path of a cloud variable, such as `U-user.namespace.variable`.
+///
+/// The first part is the ID of the user or group that owns the variable's
+/// definition, and the rest is the variable's subpath.
+///
+/// # Example usage
+///
+/// ```
+/// use resonite::model::CloudVariablePath;
+/// let path = CloudVariablePath::try_from("G-Resonite.world.visits").unwrap();
+/// assert_eq!(path.definition_owner_id.as_ref(), "G-Resonite");
+/// assert_eq!(path.subpath, "world.visits");
+/// assert_eq!(path.to_string(), "G-Resonite.world.visits");
+/// ```
+pub struct CloudVariablePath {
+	/// The owner of the variable's definition
+	pub definition_owner_id: crate::id::Owner,
+	/// The path of the variable within the owner's variables
+	pub subpath: String,
+}
+
+impl TryFrom<&str> for CloudVariablePath {
+	type Error = &'static str;
+
+	fn try_from(path: &str) -> Result<Self, Self::Error> {
+		let (owner, subpath) =
+			path.split_once('.').ok_or("should contain a `.` after the owner ID")?;
+		if subpath.is_empty() {
+			return Err("should have a subpath after the owner ID");
+		}
+
+		let definition_owner_id = if owner.starts_with("U-") {
+			crate::id::User::try_from(owner.to_owned())?.into()
+		} else {
+			crate::id::Group::try_from(owner.to_owned())
+				.map_err(|_| "should start with an `U-` or `G-` owner ID")?
+				.into()
+		};
+
+		Ok(Self { definition_owner_id, subpath: subpath.to_owned() })
+	}
+}
+
+impl Display for CloudVariablePath {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}.{}", self.definition_owner_id.as_ref(), self.subpath)
+	}
+}
+
+impl<'de> serde::de::Deserialize<'de> for CloudVariablePath {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::de::Deserializer<'de>,
+	{
+		let path = String::deserialize(deserializer)?;
+		Self::try_from(path.as_str()).map_err(|err| {
+			serde::de::Error::invalid_value(serde::de::Unexpected::Str(&path), &err)
+		})
+	}
+}
+
+impl serde::ser::Serialize for CloudVariablePath {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::ser::Serializer,
+	{
+		serializer.serialize_str(&self.to_string())
+	}
+}
+
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[serde_with::serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+/// The value of a cloud variable for a specific owner
+pub struct CloudVariable {
+	/// The user or group that the value is for
+	pub owner_id: crate::id::Owner,
+	/// The path of the variable
+	pub path: CloudVariablePath,
+	#[cfg_attr(
+		feature = "borsh",
+		borsh(
+			serialize_with = "crate::util::borsh::time::optional_ser",
+			deserialize_with = "crate::util::borsh::time::optional_de"
+		)
+	)]
+	#[serde(default)]
+	#[serde(with = "crate::util::opt_rfc3339")]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	/// When the value was last changed
+	pub timestamp: Option<OffsetDateTime>,
+	#[serde_as(deserialize_as = "serde_with::DefaultOnNull")]
+	#[serde(default)]
+	/// The value as a string, see
+	/// [`typed_value`](CloudVariable::typed_value) for parsing it.
+	///
+	/// Defaults to an empty string if missing
+	pub value: String,
+}
+
+impl CloudVariable {
+	/// Creates a new cloud variable value, with the timestamp set to now
+	pub fn new(
+		owner_id: impl Into<crate::id::Owner>, path: CloudVariablePath,
+		value: &CloudVariableValue,
+	) -> Self {
+		Self {
+			owner_id: owner_id.into(),
+			path,
+			timestamp: Some(OffsetDateTime::now_utc()),
+			value: value.to_string(),
+		}
+	}
+
+	/// Parses the value based on the type of the variable's definition
+	///
+	/// # Errors
+	///
+	/// If the value isn't valid for the type
+	pub fn typed_value(
+		&self, variable_type: CloudVariableType,
+	) -> Result<CloudVariableValue, &'static str> {
+		CloudVariableValue::parse(variable_type, &self.value)
+	}
+}
+
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[serde_with::serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+/// The definition of a cloud variable, which specifies its type and access
+pub struct CloudVariableDefinition {
+	/// If anyone can read the variable
+	pub anyone_can_read: bool,
+	/// If anyone can write the variable
+	pub anyone_can_write: bool,
+	#[serde_as(deserialize_as = "serde_with::DefaultOnNull")]
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	/// The value that the variable has when it hasn't been set yet
+	pub default_value: Option<String>,
+	/// The user or group that owns the definition
+	pub definition_owner_id: crate::id::Owner,
+	/// The path of the variable within the owner's variables
+	pub subpath: String,
+	/// The type of the variable's values
+	pub type_hint: CloudVariableType,
+	/// If the owner of the value can read it
+	pub variable_owner_can_read: bool,
+	/// If the owner of the value can write it
+	pub variable_owner_can_write: bool,
+}
+
+impl CloudVariableDefinition {
+	#[must_use]
+	/// The full path of the variable
+	pub fn path(&self) -> CloudVariablePath {
+		CloudVariablePath {
+			definition_owner_id: self.definition_owner_id.clone(),
+			subpath: self.subpath.clone(),
+		}
+	}
+}
+
+#[repr(u8)]
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "borsh", borsh(use_discriminant = true))]
+#[derive(
+	Debug,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	Hash,
+	Deserialize,
+	Serialize,
+	strum::Display,
+	strum::EnumString,
+	strum::AsRefStr,
+	strum::VariantNames,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+/// The type of a cloud variable's values
+// Serde requires the catch-all variant to be the last one
+#[allow(clippy::arbitrary_source_item_ordering)]
+pub enum CloudVariableType {
+	/// `true` or `false`
+	Bool = 2,
+	/// A 64 bit floating point number
+	Double = 5,
+	/// A 32 bit floating point number
+	Float = 4,
+	/// A 32 bit integer
+	Int = 1,
+	/// A 64 bit integer
+	Long = 3,
+	/// Any text
+	String = 0,
+	/// An URI, such as `resdb:///` or `https://` links
+	Uri = 6,
+	/// Not yet supported type, whose values are handled as text
+	#[serde(other)]
+	Unknown = 255,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A parsed value of a cloud variable
+pub enum CloudVariableValue {
+	/// `true` or `false`
+	Bool(bool),
+	/// A 64 bit floating point number
+	Double(f64),
+	/// A 32 bit floating point number
+	Float(f32),
+	/// A 32 bit integer
+	Int(i32),
+	/// A 64 bit integer
+	Long(i64),
+	/// Any text
+	String(String),
+	/// An URI, such as `resdb:///` or `https://` links
+	Uri(String),
+}
+
+impl CloudVariableValue {
+	/// Parses a raw value of a cloud variable
+	///
+	/// # Errors
+	///
+	/// If the value isn't valid for the type
+	pub fn parse(
+		variable_type: CloudVariableType, value: &str,
+	) -> Result<Self, &'static str> {
+		Ok(match variable_type {
+			CloudVariableType::Bool => match value.to_ascii_lowercase().as_str() {
+				"true" => Self::Bool(true),
+				"false" => Self::Bool(false),
+				_ => return Err("should be `true` or `false`"),
+			},
+			CloudVariableType::Double => {
+				Self::Double(value.parse().map_err(|_| "should be a number")?)
+			}
+			CloudVariableType::Float => {
+				Self::Float(value.parse().map_err(|_| "should be a number")?)
+			}
+			CloudVariableType::Int => {
+				Self::Int(value.parse().map_err(|_| "should be a 32 bit integer")?)
+			}
+			CloudVariableType::Long => {
+				Self::Long(value.parse().map_err(|_| "should be a 64 bit integer")?)
+			}
+			CloudVariableType::String | CloudVariableType::Unknown => {
+				Self::String(value.to_owned())
+			}
+			CloudVariableType::Uri => {
+				if !value.is_empty() && !value.contains(':') {
+					return Err("should be an URI with a scheme");
+				}
+				Self::Uri(value.to_owned())
+			}
+		})
+	}
+
+	#[must_use]
+	/// The type of the value
+	pub const fn variable_type(&self) -> CloudVariableType {
+		match self {
+			Self::Bool(_) => CloudVariableType::Bool,
+			Self::Double(_) => CloudVariableType::Double,
+			Self::Float(_) => CloudVariableType::Float,
+			Self::Int(_) => CloudVariableType::Int,
+			Self::Long(_) => CloudVariableType::Long,
+			Self::String(_) => CloudVariableType::String,
+			Self::Uri(_) => CloudVariableType::Uri,
+		}
+	}
+}
+
+impl Display for CloudVariableValue {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Bool(v) => write!(f, "{v}"),
+			Self::Double(v) => write!(f, "{v}"),
+			Self::Float(v) => write!(f, "{v}"),
+			Self::Int(v) => write!(f, "{v}"),
+			Self::Long(v) => write!(f, "{v}"),
+			Self::String(v) | Self::Uri(v) => write!(f, "{v}"),
+		}
+	}
+}
+
+#[cfg(test)]
+#[test]
+fn cloud_variable() {
+	let json = serde_json::json!({
+		"ownerId": "U-owner",
+		"path": "G-Resonite.test.counter",
+		"value": "42",
+		"timestamp": "2024-07-28T22:00:44.403632Z",
+	});
+	let variable: CloudVariable = serde_json::from_value(json.clone()).unwrap();
+	assert_eq!(variable.path.subpath, "test.counter");
+	assert_eq!(
+		variable.typed_value(CloudVariableType::Int).unwrap(),
+		CloudVariableValue::Int(42)
+	);
+	assert!(variable.typed_value(CloudVariableType::Bool).is_err());
+	assert_eq!(serde_json::to_value(&variable).unwrap(), json);
+
+	let value =
+		CloudVariableValue::parse(CloudVariableType::Bool, "True").unwrap();
+	assert_eq!(value.to_string(), "true");
+	assert!(CloudVariablePath::try_from("U-owner").is_err());
+	assert!(CloudVariablePath::try_from("R-record.path").is_err());
+
+	let definition: CloudVariableDefinition =
+		serde_json::from_value(serde_json::json!({
+			"anyoneCanRead": true,
+			"anyoneCanWrite": false,
+			"definitionOwnerId": "G-Resonite",
+			"subpath": "test.color",
+			"typeHint": "colorX",
+			"variableOwnerCanRead": true,
+			"variableOwnerCanWrite": true,
+		}))
+		.unwrap();
+	assert_eq!(definition.type_hint, CloudVariableType::Unknown);
+	assert_eq!(
+		variable.typed_value(definition.type_hint).unwrap(),
+		CloudVariableValue::String("42".to_owned())
+	);
+}
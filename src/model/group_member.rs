@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[serde_with::serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+/// A member of a Resonite group, called `Member` in the API.
+pub struct GroupMember {
+	#[serde(rename = "ownerId")]
+	/// The G-groupname form of ID of the group
+	pub group_id: crate::id::Group,
+	/// The U-username form of ID of the member
+	pub id: crate::id::User,
+	#[serde_as(deserialize_as = "serde_with::DefaultOnNull")]
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	/// How much of the group's storage quota the member is allowed to use
+	pub quota_bytes: Option<u64>,
+	#[serde_as(deserialize_as = "serde_with::DefaultOnNull")]
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	/// How much of the group's storage the member has used
+	pub used_bytes: Option<u64>,
+}
+
+impl GroupMember {
+	#[must_use]
+	/// The role of the member in the group
+	pub fn role(&self, group: &crate::model::Group) -> GroupMemberRole {
+		if self.id == group.admin_id {
+			GroupMemberRole::Admin
+		} else {
+			GroupMemberRole::Member
+		}
+	}
+}
+
+#[repr(u8)]
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "borsh", borsh(use_discriminant = true))]
+#[derive(
+	Debug,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	Hash,
+	PartialOrd,
+	Ord,
+	Deserialize,
+	Serialize,
+	strum::Display,
+	strum::EnumString,
+	strum::AsRefStr,
+	strum::VariantNames,
+)]
+/// The role of a member in a group
+pub enum GroupMemberRole {
+	/// The admin of the group
+	Admin = 1,
+	/// A normal member of the group
+	Member = 0,
+}
@@ -7,10 +7,14 @@
 #![allow(clippy::module_name_repetitions)]
 
 mod assembly_info;
+mod asset_metadata;
+mod asset_variant;
+mod cloud_variable;
 mod contact;
 mod contact_status;
 mod db_asset;
 mod group;
+mod group_member;
 mod message;
 mod online_status;
 mod output_device;
@@ -22,7 +26,10 @@ mod session_access_level;
 mod session_user;
 mod sessions_info;
 mod stats;
+mod stats_history;
+mod storage;
 mod submission;
+mod two_factor;
 mod user;
 mod user_profile;
 mod user_session;
@@ -30,10 +37,14 @@ mod user_session_metadata;
 mod user_status;
 
 pub use assembly_info::*;
+pub use asset_metadata::*;
+pub use asset_variant::*;
+pub use cloud_variable::*;
 pub use contact::*;
 pub use contact_status::*;
 pub use db_asset::*;
 pub use group::*;
+pub use group_member::*;
 pub use message::*;
 pub use online_status::*;
 pub use output_device::*;
@@ -45,7 +56,10 @@ pub use session_access_level::*;
 pub use session_user::*;
 pub use sessions_info::*;
 pub use stats::*;
+pub use stats_history::*;
+pub use storage::*;
 pub use submission::*;
+pub use two_factor::*;
 pub use user::*;
 pub use user_profile::*;
 pub use user_session::*;
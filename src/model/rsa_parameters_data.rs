@@ -7,23 +7,32 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 /// A Resonite users public RSA key pair...for... session authentication?
+///
+/// The aliases are the keys as they are with the `SignalR` `MessagePack`
+/// protocol, which lower cases their first letters.
 pub struct RSAParametersData {
+	#[serde(alias = "d")]
 	/// Who knows...
 	pub d: String,
-	#[serde(rename = "DP")]
+	#[serde(rename = "DP", alias = "dP")]
 	/// Who knows...
 	pub dp: String,
-	#[serde(rename = "DQ")]
+	#[serde(rename = "DQ", alias = "dQ")]
 	/// Who knows...
 	pub dq: String,
+	#[serde(alias = "exponent")]
 	/// The exponent component of the RSA public key
 	pub exponent: String,
+	#[serde(alias = "inverseQ")]
 	/// Who knows...
 	pub inverse_q: String,
+	#[serde(alias = "modulus")]
 	/// The modulus component of the RSA public key
 	pub modulus: String,
+	#[serde(alias = "p")]
 	/// Who knows...
 	pub p: String,
+	#[serde(alias = "q")]
 	/// Who knows...
 	pub q: String,
 }
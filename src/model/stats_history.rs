@@ -0,0 +1,275 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use super::{CloudStatistics, OnlineStatistics, SessionAccessLevel};
+
+/// A snapshot of statistics that was captured at some point in time
+pub trait StatisticsSnapshot {
+	/// When the statistics were captured
+	fn capture_timestamp(&self) -> OffsetDateTime;
+}
+
+impl StatisticsSnapshot for CloudStatistics {
+	fn capture_timestamp(&self) -> OffsetDateTime { self.capture_timestamp }
+}
+
+impl StatisticsSnapshot for OnlineStatistics {
+	fn capture_timestamp(&self) -> OffsetDateTime { self.capture_timestamp }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// How much a statistic changed between two snapshots
+pub struct StatisticsChange {
+	/// How much the value changed
+	pub change: i64,
+	/// How much time passed between the snapshots
+	pub elapsed: time::Duration,
+	/// When the later snapshot was captured
+	pub timestamp: OffsetDateTime,
+}
+
+impl StatisticsChange {
+	#[must_use]
+	/// The change scaled to be per minute
+	pub fn per_minute(&self) -> f64 {
+		let minutes = self.elapsed.as_seconds_f64() / 60.0;
+		if minutes <= 0.0 {
+			return 0.0;
+		}
+		#[allow(clippy::cast_precision_loss)]
+		let change = self.change as f64;
+		change / minutes
+	}
+}
+
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(try_from = "StatisticsHistoryData<T>")]
+#[serde(bound(deserialize = "T: Deserialize<'de> + StatisticsSnapshot"))]
+/// A bounded in-memory time series of statistics snapshots.
+///
+/// Once full, the oldest snapshots are dropped to make space for new ones.
+/// Can be (de)serialized to persist it between restarts, with deserialized
+/// snapshots being sorted and truncated the same way as pushed ones, and a
+/// capacity of zero being rejected.
+pub struct StatisticsHistory<T> {
+	capacity: usize,
+	snapshots: VecDeque<T>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// The unvalidated data of a deserialized [`StatisticsHistory`]
+struct StatisticsHistoryData<T> {
+	capacity: usize,
+	snapshots: Vec<T>,
+}
+
+impl<T: StatisticsSnapshot> TryFrom<StatisticsHistoryData<T>>
+	for StatisticsHistory<T>
+{
+	type Error = &'static str;
+
+	fn try_from(data: StatisticsHistoryData<T>) -> Result<Self, Self::Error> {
+		if data.capacity == 0 {
+			return Err("statistics history capacity should be larger than zero");
+		}
+
+		Ok(Self::from_snapshots(data.capacity, data.snapshots))
+	}
+}
+
+#[cfg(feature = "borsh")]
+impl<T: borsh::BorshDeserialize + StatisticsSnapshot> borsh::BorshDeserialize
+	for StatisticsHistory<T>
+{
+	fn deserialize_reader<R: borsh::io::Read>(
+		reader: &mut R,
+	) -> borsh::io::Result<Self> {
+		let capacity = usize::deserialize_reader(reader)?;
+		let snapshots = Vec::<T>::deserialize_reader(reader)?;
+		StatisticsHistoryData { capacity, snapshots }
+			.try_into()
+			.map_err(|e| borsh::io::Error::new(borsh::io::ErrorKind::InvalidData, e))
+	}
+}
+
+impl<T: StatisticsSnapshot> StatisticsHistory<T> {
+	#[must_use]
+	/// How many snapshots can be stored at most
+	pub const fn capacity(&self) -> usize { self.capacity }
+
+	/// The changes of a value between each consecutive snapshot
+	pub fn changes(&self, value: impl Fn(&T) -> i64) -> Vec<StatisticsChange> {
+		self
+			.snapshots
+			.iter()
+			.zip(self.snapshots.iter().skip(1))
+			.map(|(previous, next)| StatisticsChange {
+				change: value(next) - value(previous),
+				elapsed: next.capture_timestamp() - previous.capture_timestamp(),
+				timestamp: next.capture_timestamp(),
+			})
+			.collect()
+	}
+
+	#[must_use]
+	/// Creates a history from snapshots in any order.
+	///
+	/// The snapshots are sorted chronologically, ones with duplicate timestamps
+	/// are skipped, and the oldest ones are dropped if over capacity.
+	pub fn from_snapshots(
+		capacity: usize, snapshots: impl IntoIterator<Item = T>,
+	) -> Self {
+		let mut snapshots: Vec<T> = snapshots.into_iter().collect();
+		snapshots.sort_by_key(StatisticsSnapshot::capture_timestamp);
+
+		let mut history = Self::new(capacity);
+		for snapshot in snapshots {
+			history.push(snapshot);
+		}
+
+		history
+	}
+
+	#[must_use]
+	/// If there are no snapshots
+	pub fn is_empty(&self) -> bool { self.snapshots.is_empty() }
+
+	#[must_use]
+	/// The most recent snapshot
+	pub fn latest(&self) -> Option<&T> { self.snapshots.back() }
+
+	#[must_use]
+	/// How many snapshots there are
+	pub fn len(&self) -> usize { self.snapshots.len() }
+
+	#[must_use]
+	/// Creates an empty history that keeps up to the capacity of snapshots.
+	///
+	/// Nothing is allocated upfront, so the capacity can be large.
+	pub const fn new(capacity: usize) -> Self {
+		Self { capacity, snapshots: VecDeque::new() }
+	}
+
+	/// Adds a snapshot, dropping the oldest ones if over capacity.
+	///
+	/// Returns false if the snapshot wasn't newer than the latest one, which
+	/// happens when the API returns cached statistics.
+	pub fn push(&mut self, snapshot: T) -> bool {
+		if self.latest().is_some_and(|latest| {
+			latest.capture_timestamp() >= snapshot.capture_timestamp()
+		}) {
+			return false;
+		}
+
+		self.snapshots.push_back(snapshot);
+		while self.snapshots.len() > self.capacity {
+			self.snapshots.pop_front();
+		}
+
+		true
+	}
+
+	#[must_use]
+	/// The snapshots from oldest to newest
+	pub fn snapshots(&self) -> impl DoubleEndedIterator<Item = &T> {
+		self.snapshots.iter()
+	}
+}
+
+impl StatisticsHistory<OnlineStatistics> {
+	#[must_use]
+	/// The changes of the count of users in sessions of the access level
+	pub fn access_level_changes(
+		&self, access_level: &SessionAccessLevel,
+	) -> Vec<StatisticsChange> {
+		self.changes(|snapshot| {
+			snapshot
+				.user_count_by_session_access_level
+				.get(access_level)
+				.copied()
+				.unwrap_or_default()
+				.into()
+		})
+	}
+
+	#[must_use]
+	/// The changes of the count of registered users that are online
+	pub fn user_count_changes(&self) -> Vec<StatisticsChange> {
+		self.changes(|snapshot| snapshot.registered_user_count.into())
+	}
+}
+
+#[cfg(test)]
+#[test]
+fn statistics_history() {
+	let start = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+	let snapshot = |minutes: i64, variants: u32| CloudStatistics {
+		asset_metadata_jobs: 0,
+		asset_variant_jobs: 0,
+		capture_timestamp: start + time::Duration::minutes(minutes),
+		computed_asset_variants: variants,
+		migration_records_per_minute: 0.0,
+		migration_tasks_in_queue: 0.0,
+		record_preprocess_jobs: 0,
+		upload_jobs: 0,
+	};
+
+	let mut history = StatisticsHistory::new(3);
+	assert!(history.push(snapshot(0, 10)));
+	assert!(history.push(snapshot(2, 20)));
+	assert!(!history.push(snapshot(2, 30)));
+	assert!(history.push(snapshot(3, 15)));
+	assert!(history.push(snapshot(5, 25)));
+	assert_eq!(history.len(), 3);
+
+	let changes =
+		history.changes(|snapshot| snapshot.computed_asset_variants.into());
+	assert_eq!(
+		changes.iter().map(|change| change.change).collect::<Vec<_>>(),
+		vec![-5, 10]
+	);
+	assert!((changes[1].per_minute() - 5.0).abs() < f64::EPSILON);
+
+	let json = serde_json::to_string(&history).unwrap();
+	let deserialized: StatisticsHistory<CloudStatistics> =
+		serde_json::from_str(&json).unwrap();
+	assert_eq!(deserialized, history);
+
+	let mut json = serde_json::to_value(&history).unwrap();
+	json["capacity"] = 2.into();
+	json["snapshots"].as_array_mut().unwrap().reverse();
+	let deserialized: StatisticsHistory<CloudStatistics> =
+		serde_json::from_value(json).unwrap();
+	assert_eq!(deserialized.capacity(), 2);
+	assert_eq!(
+		deserialized
+			.snapshots()
+			.map(|snapshot| snapshot.computed_asset_variants)
+			.collect::<Vec<_>>(),
+		vec![15, 25]
+	);
+
+	// The capacity doesn't allocate anything, but can't be zero
+	let mut json = serde_json::to_value(&history).unwrap();
+	json["capacity"] = usize::MAX.into();
+	let deserialized: StatisticsHistory<CloudStatistics> =
+		serde_json::from_value(json.clone()).unwrap();
+	assert_eq!(deserialized.len(), 3);
+	json["capacity"] = 0.into();
+	assert!(
+		serde_json::from_value::<StatisticsHistory<CloudStatistics>>(json).is_err()
+	);
+	assert_eq!(
+		StatisticsHistory::from_snapshots(
+			3,
+			[snapshot(1, 1), snapshot(0, 0), snapshot(1, 2)]
+		)
+		.len(),
+		2
+	);
+}
@@ -0,0 +1,144 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// How full the storage is allowed to get before warning about it
+const STORAGE_WARNING_RATIO: f64 = 0.9;
+
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[serde_with::serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+/// The storage quota and usage of an user or group
+pub struct Storage {
+	#[serde_as(deserialize_as = "serde_with::DefaultOnNull")]
+	#[serde(default)]
+	#[serde(skip_serializing_if = "BTreeMap::is_empty")]
+	/// How many bytes are used by different kinds of content, if provided.
+	///
+	/// Defaults to being empty
+	pub breakdown: BTreeMap<String, u64>,
+	#[serde_as(deserialize_as = "serde_with::DefaultOnNull")]
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	/// The quota including any bonuses, such as from supporting Resonite
+	pub full_quota_bytes: Option<u64>,
+	/// The user or group that the storage belongs to
+	pub owner_id: crate::id::Owner,
+	/// How large the storage quota is
+	pub quota_bytes: u64,
+	/// How much of the storage quota is used
+	pub used_bytes: u64,
+}
+
+impl Storage {
+	#[must_use]
+	/// Checks if an upload of the size would fit into the storage quota
+	pub fn check_upload(&self, upload_bytes: u64) -> StorageCheck {
+		let remaining_bytes = self.remaining_bytes();
+		if upload_bytes > remaining_bytes {
+			return StorageCheck::ExceedsQuota {
+				missing_bytes: upload_bytes - remaining_bytes,
+			};
+		}
+
+		let remaining_bytes = remaining_bytes - upload_bytes;
+		#[allow(clippy::cast_precision_loss)]
+		let used_ratio = (self.used_bytes.saturating_add(upload_bytes)) as f64
+			/ self.total_quota_bytes() as f64;
+		if used_ratio >= STORAGE_WARNING_RATIO {
+			StorageCheck::NearQuota { remaining_bytes }
+		} else {
+			StorageCheck::Fits { remaining_bytes }
+		}
+	}
+
+	#[must_use]
+	/// How many bytes can still be used before reaching the quota
+	pub const fn remaining_bytes(&self) -> u64 {
+		self.total_quota_bytes().saturating_sub(self.used_bytes)
+	}
+
+	#[must_use]
+	/// The quota that the storage is limited by, including any bonuses
+	pub const fn total_quota_bytes(&self) -> u64 {
+		match self.full_quota_bytes {
+			Some(full_quota_bytes) => full_quota_bytes,
+			None => self.quota_bytes,
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// The result of checking if an upload fits into the storage quota
+pub enum StorageCheck {
+	/// The upload wouldn't fit into the quota
+	ExceedsQuota {
+		/// How many more bytes would be needed for the upload to fit
+		missing_bytes: u64,
+	},
+	/// The upload fits without problems
+	Fits {
+		/// How many bytes would be left after the upload
+		remaining_bytes: u64,
+	},
+	/// The upload fits, but the storage would be nearly full afterwards
+	NearQuota {
+		/// How many bytes would be left after the upload
+		remaining_bytes: u64,
+	},
+}
+
+impl StorageCheck {
+	#[must_use]
+	/// If the upload would fit into the quota
+	pub const fn fits(&self) -> bool {
+		!matches!(self, Self::ExceedsQuota { .. })
+	}
+
+	#[must_use]
+	/// If the user should be warned about the upload
+	pub const fn should_warn(&self) -> bool { !matches!(self, Self::Fits { .. }) }
+}
+
+#[cfg(test)]
+#[test]
+fn storage() {
+	let json = serde_json::json!({
+		"ownerId": "G-group",
+		"quotaBytes": 1000,
+		"usedBytes": 800,
+		"breakdown": { "records": 500, "assets": 300 },
+	});
+	let storage: Storage = serde_json::from_value(json.clone()).unwrap();
+	assert_eq!(storage.remaining_bytes(), 200);
+	assert_eq!(storage.breakdown.get("assets"), Some(&300));
+	assert_eq!(serde_json::to_value(&storage).unwrap(), json);
+
+	assert_eq!(
+		storage.check_upload(50),
+		StorageCheck::Fits { remaining_bytes: 150 }
+	);
+	let check = storage.check_upload(150);
+	assert_eq!(check, StorageCheck::NearQuota { remaining_bytes: 50 });
+	assert!(check.fits() && check.should_warn());
+	let check = storage.check_upload(250);
+	assert_eq!(check, StorageCheck::ExceedsQuota { missing_bytes: 50 });
+	assert!(!check.fits());
+
+	// Bonus storage raises the quota
+	let storage = Storage { full_quota_bytes: Some(2000), ..storage };
+	assert_eq!(storage.total_quota_bytes(), 2000);
+	assert_eq!(storage.remaining_bytes(), 1200);
+	assert_eq!(
+		storage.check_upload(250),
+		StorageCheck::Fits { remaining_bytes: 950 }
+	);
+	assert_eq!(
+		storage.check_upload(1100),
+		StorageCheck::NearQuota { remaining_bytes: 100 }
+	);
+}
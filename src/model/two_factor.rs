@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[serde_with::serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+/// The details for setting up TOTP based two factor authentication.
+///
+/// Two factor authentication isn't enabled until a code generated with the
+/// secret has been verified.
+pub struct TotpEnrollment {
+	#[serde_as(deserialize_as = "serde_with::DefaultOnNull")]
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	/// Single use codes that can be used instead of TOTP codes
+	///
+	/// Defaults to being empty
+	pub recovery_codes: Vec<String>,
+	/// The base32 encoded shared secret
+	pub secret: String,
+	/// The `otpauth://` URI of the secret, usually shown as a QR code
+	pub uri: String,
+}
+
+impl TotpEnrollment {
+	#[cfg(feature = "totp_util")]
+	/// Generates the current TOTP code for the secret
+	///
+	/// # Errors
+	///
+	/// If the secret isn't valid base32
+	pub fn current_code(&self) -> Result<String, &'static str> {
+		crate::util::totp::now(&self.secret)
+	}
+}
+
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+/// Newly generated recovery codes for two factor authentication
+pub struct RecoveryCodes {
+	/// Single use codes that can be used instead of TOTP codes
+	pub recovery_codes: Vec<String>,
+}
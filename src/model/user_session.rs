@@ -1,3 +1,5 @@
+use std::path::{Path, PathBuf};
+
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use time::{OffsetDateTime, serde::rfc3339};
@@ -109,6 +111,91 @@ impl UserSession {
 	pub fn auth_header(&self) -> String {
 		"res ".to_owned() + self.user_id.as_ref() + ":" + &self.token
 	}
+
+	#[must_use]
+	/// If the user session has already expired
+	pub fn is_expired(&self) -> bool {
+		self.expiration_time <= OffsetDateTime::now_utc()
+	}
+}
+
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+/// An active user session of the account, without any of its secrets.
+///
+/// The response from the API at GET `users/{userId}/userSessions`.
+pub struct UserSessionInfo {
+	#[cfg_attr(
+		feature = "borsh",
+		borsh(
+			serialize_with = "crate::util::borsh::time::optional_ser",
+			deserialize_with = "crate::util::borsh::time::optional_de"
+		)
+	)]
+	#[serde(rename = "created")]
+	#[serde(default)]
+	#[serde(with = "crate::util::opt_rfc3339")]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	/// When the user session was created
+	pub creation_time: Option<OffsetDateTime>,
+	#[cfg_attr(
+		feature = "borsh",
+		borsh(
+			serialize_with = "crate::util::borsh::time::optional_ser",
+			deserialize_with = "crate::util::borsh::time::optional_de"
+		)
+	)]
+	#[serde(rename = "expire")]
+	#[serde(default)]
+	#[serde(with = "crate::util::opt_rfc3339")]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	/// When the user session is set to expire
+	pub expiration_time: Option<OffsetDateTime>,
+	#[serde_as(deserialize_as = "serde_with::DefaultOnNull")]
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	/// The ID of the user session, which can be used to revoke it
+	pub id: Option<crate::id::UserSession>,
+	#[serde_as(deserialize_as = "serde_with::DefaultOnNull")]
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	/// If the user session has is bound to the specific machine ID
+	pub is_machine_bound: Option<bool>,
+	#[serde_as(deserialize_as = "serde_with::DefaultOnNull")]
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	/// How the user session was originally created
+	pub original_login_type: Option<UserSessionLoginType>,
+	#[serde_as(deserialize_as = "serde_with::DefaultOnNull")]
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	/// If the user session has the remember me checked (lives longer)
+	pub remember_me: Option<bool>,
+	#[serde_as(deserialize_as = "serde_with::DefaultOnNull")]
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	/// How many times the session has been used
+	pub session_login_counter: Option<u64>,
+	#[serde_as(deserialize_as = "serde_with::DefaultOnNull")]
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	/// The Resonite user that this session is for
+	pub user_id: Option<crate::id::User>,
+}
+
+impl UserSessionInfo {
+	#[must_use]
+	/// If the user session has already expired
+	pub fn is_expired(&self) -> bool {
+		self.expiration_time.is_some_and(|expiration_time| {
+			expiration_time <= OffsetDateTime::now_utc()
+		})
+	}
 }
 
 #[repr(u8)]
@@ -158,12 +245,113 @@ impl Default for UserSessionLoginType {
 #[serde(rename_all = "camelCase")]
 /// Config file data that's returned when requesting an user session
 pub struct ConfigFileData {
-	/// Supposedly path to where the config file should be stored
+	/// The contents of the config file
 	pub content: String,
 	/// Supposedly path to where the config file should be stored
 	pub path: String,
 }
 
+impl ConfigFileData {
+	/// Creates config file data from JSON serializable content
+	///
+	/// # Errors
+	///
+	/// If serializing the content fails
+	pub fn from_json<T: Serialize>(
+		path: impl Into<String>, content: &T,
+	) -> serde_json::Result<Self> {
+		Ok(Self {
+			content: serde_json::to_string_pretty(content)?,
+			path: path.into(),
+		})
+	}
+
+	/// Parses the content of the config file as JSON
+	///
+	/// # Errors
+	///
+	/// If the content isn't valid JSON for the type
+	pub fn json_content<T: serde::de::DeserializeOwned>(
+		&self,
+	) -> serde_json::Result<T> {
+		serde_json::from_str(&self.content)
+	}
+
+	/// Writes the config file under the target directory.
+	///
+	/// Returns the path that the file was written to.
+	///
+	/// # Errors
+	///
+	/// If the path isn't safe or writing the file fails
+	pub fn materialize(
+		&self, target_directory: impl AsRef<Path>,
+	) -> std::io::Result<PathBuf> {
+		let relative_path = self.sanitized_path().ok_or_else(|| {
+			std::io::Error::new(
+				std::io::ErrorKind::InvalidInput,
+				format!("Unsafe config file path: {}", self.path),
+			)
+		})?;
+		let path = target_directory.as_ref().join(relative_path);
+
+		if let Some(parent) = path.parent() {
+			std::fs::create_dir_all(parent)?;
+		}
+		std::fs::write(&path, &self.content)?;
+
+		Ok(path)
+	}
+
+	/// Reads a config file that's under the target directory.
+	///
+	/// The opposite of [`materialize`](Self::materialize).
+	///
+	/// # Errors
+	///
+	/// If the path isn't safe or reading the file fails
+	pub fn read(
+		target_directory: impl AsRef<Path>, path: impl Into<String>,
+	) -> std::io::Result<Self> {
+		let mut config_file = Self { content: String::new(), path: path.into() };
+		let relative_path = config_file.sanitized_path().ok_or_else(|| {
+			std::io::Error::new(
+				std::io::ErrorKind::InvalidInput,
+				format!("Unsafe config file path: {}", config_file.path),
+			)
+		})?;
+		config_file.content =
+			std::fs::read_to_string(target_directory.as_ref().join(relative_path))?;
+
+		Ok(config_file)
+	}
+
+	#[must_use]
+	/// The path as a relative one that can't point outside of a directory.
+	///
+	/// Both `/` and `\` are treated as separators, and `.` components are
+	/// skipped. Returns `None` for absolute paths, paths with `..` components,
+	/// or paths that would point to the directory itself.
+	pub fn sanitized_path(&self) -> Option<PathBuf> {
+		let mut path = PathBuf::new();
+		if self.path.starts_with(['/', '\\']) {
+			return None;
+		}
+
+		for component in self.path.split(['/', '\\']) {
+			match component {
+				"" | "." => {}
+				".." => return None,
+				// Drive letters and such on Windows
+				component if component.contains(':') => return None,
+				component => path.push(component),
+			}
+		}
+
+		if path.as_os_str().is_empty() { None } else { Some(path) }
+	}
+}
+
 impl std::fmt::Debug for ConfigFileData {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		f.debug_struct("ConfigFileData")
@@ -191,6 +379,36 @@ pub struct UserSessionResult {
 	pub user_session: UserSession,
 }
 
+impl UserSessionResult {
+	/// Writes all the config files under the target directory.
+	///
+	/// Returns the paths that the files were written to.
+	///
+	/// # Errors
+	///
+	/// If any of the paths aren't safe or writing a file fails
+	pub fn materialize_config_files(
+		&self, target_directory: impl AsRef<Path>,
+	) -> std::io::Result<Vec<PathBuf>> {
+		let target_directory = target_directory.as_ref();
+		// Validating everything first to avoid leaving partial results behind
+		if let Some(config_file) =
+			self.config_files.iter().find(|file| file.sanitized_path().is_none())
+		{
+			return Err(std::io::Error::new(
+				std::io::ErrorKind::InvalidInput,
+				format!("Unsafe config file path: {}", config_file.path),
+			));
+		}
+
+		self
+			.config_files
+			.iter()
+			.map(|config_file| config_file.materialize(target_directory))
+			.collect()
+	}
+}
+
 #[repr(u8)]
 #[cfg_attr(
 	feature = "borsh",
@@ -230,3 +448,87 @@ pub enum UserSessionType {
 impl Default for UserSessionType {
 	fn default() -> Self { Self::Unknown }
 }
+
+#[cfg(test)]
+#[test]
+fn config_file_data() {
+	let directory = std::env::temp_dir()
+		.join(format!("resonite-config-files-test-{}", std::process::id()));
+
+	let sanitized = |path: &str| {
+		ConfigFileData { content: String::new(), path: path.to_owned() }
+			.sanitized_path()
+	};
+	assert_eq!(
+		sanitized("Config/./a.json"),
+		Some(PathBuf::from("Config/a.json"))
+	);
+	assert_eq!(sanitized("Config\\a.json"), Some(PathBuf::from("Config/a.json")));
+	assert_eq!(sanitized("../a.json"), None);
+	assert_eq!(sanitized("Config/../../a.json"), None);
+	assert_eq!(sanitized("/etc/passwd"), None);
+	assert_eq!(sanitized("C:\\a.json"), None);
+	assert_eq!(sanitized("./"), None);
+
+	let config_file = ConfigFileData::from_json(
+		"Config/settings.json",
+		&serde_json::json!({ "enabled": true }),
+	)
+	.unwrap();
+	let path = config_file.materialize(&directory).unwrap();
+	assert!(path.starts_with(&directory));
+	let read = ConfigFileData::read(&directory, "Config/settings.json").unwrap();
+	assert_eq!(read, config_file);
+	let content: serde_json::Value = read.json_content().unwrap();
+	assert_eq!(content["enabled"], true);
+
+	let bad = ConfigFileData { content: String::new(), path: "../a".to_owned() };
+	assert!(bad.materialize(&directory).is_err());
+	assert!(ConfigFileData::read(&directory, "../a").is_err());
+
+	std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[cfg(test)]
+#[test]
+fn user_session_listing() {
+	let user_sessions: Vec<UserSessionInfo> = serde_json::from_str(
+		r#"[
+  {
+    "id": "ffa04206-bf29-4c02-98cd-2664ed6aaccb",
+    "userId": "U-example",
+    "created": "2026-10-01T12:00:00.1234567Z",
+    "expire": "2099-10-31T12:00:00.1234567Z",
+    "rememberMe": true,
+    "isMachineBound": true,
+    "originalLoginType": "Password",
+    "sessionLoginCounter": 3,
+    "logoutUrlClientSide": false
+  },
+  {
+    "id": "2e0b2b1c-0f0e-4c57-9d43-9b1c0d1f7a10",
+    "userId": "U-example",
+    "created": "2026-10-17T08:30:00Z",
+    "expire": null,
+    "rememberMe": false,
+    "isMachineBound": null
+  }
+]"#,
+	)
+	.unwrap();
+
+	assert_eq!(user_sessions.len(), 2);
+	assert_eq!(
+		user_sessions[0].id.as_ref().map(AsRef::as_ref),
+		Some("ffa04206-bf29-4c02-98cd-2664ed6aaccb")
+	);
+	assert_eq!(
+		user_sessions[0].original_login_type,
+		Some(UserSessionLoginType::Password)
+	);
+	assert_eq!(user_sessions[0].session_login_counter, Some(3));
+	assert!(!user_sessions[0].is_expired());
+	assert_eq!(user_sessions[1].expiration_time, None);
+	assert_eq!(user_sessions[1].is_machine_bound, None);
+	assert_eq!(user_sessions[1].original_login_type, None);
+}
@@ -91,3 +91,35 @@ pub struct UserStatus {
 	#[serde(default)]
 	pub user_session_type: Option<UserSessionType>,
 }
+
+impl UserStatus {
+	#[must_use]
+	/// Creates a new present status for an user, with the times set to now.
+	///
+	/// Useful for publishing the status of a bot or a headless client for
+	/// example, with the sessions and other details being filled in afterwards.
+	pub fn new(
+		user_id: crate::id::User, online_status: crate::model::OnlineStatus,
+		user_session_type: UserSessionType,
+	) -> Self {
+		let now = OffsetDateTime::now_utc();
+
+		Self {
+			app_version: None,
+			compatibility_hash: None,
+			current_session_index: 0,
+			hash_salt: None,
+			is_mobile: false,
+			is_present: true,
+			last_presence_time: Some(now),
+			last_status_change_time: Some(now),
+			online_status,
+			output_device: crate::model::OutputDevice::default(),
+			public_rsa_key: None,
+			sessions: Vec::new(),
+			user_id,
+			user_session_id: None,
+			user_session_type: Some(user_session_type),
+		}
+	}
+}
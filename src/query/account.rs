@@ -0,0 +1,203 @@
+use racal::Queryable;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use super::{Authentication, NoAuthentication};
+
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[serde_with::serde_as]
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+/// Registers a new user account.
+///
+/// The account needs to have its email verified before it can be fully used.
+pub struct RegisterUser {
+	#[cfg_attr(
+		feature = "borsh",
+		borsh(
+			serialize_with = "crate::util::borsh::time::optional_ser",
+			deserialize_with = "crate::util::borsh::time::optional_de"
+		)
+	)]
+	#[serde(default)]
+	#[serde(with = "crate::util::opt_rfc3339")]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	/// The date of birth of the account's owner
+	pub date_of_birth: Option<OffsetDateTime>,
+	/// The email address of the account
+	pub email: String,
+	/// The password of the account
+	pub password: String,
+	/// The username of the account
+	pub username: String,
+}
+
+impl Queryable<NoAuthentication, crate::model::User> for RegisterUser {
+	fn url(&self, _: &NoAuthentication) -> String {
+		format!("{}/users", crate::HTTP_BASE_URI)
+	}
+
+	fn body(
+		&self, _state: &NoAuthentication,
+	) -> Option<serde_json::Result<Vec<u8>>> {
+		Some(serde_json::to_vec(self))
+	}
+
+	fn method(&self, _: &NoAuthentication) -> racal::RequestMethod {
+		racal::RequestMethod::Post
+	}
+}
+
+/// Requests the verification email to be sent again for the logged in user
+pub struct RequestEmailVerification;
+
+impl Queryable<Authentication, ()> for RequestEmailVerification {
+	fn url(&self, auth: &Authentication) -> String {
+		format!(
+			"{}/users/{}/requestEmailVerification",
+			crate::HTTP_BASE_URI,
+			auth.user_id.as_ref()
+		)
+	}
+
+	fn method(&self, _: &Authentication) -> racal::RequestMethod {
+		racal::RequestMethod::Post
+	}
+
+	fn deserialize(&self, _data: &[u8]) -> serde_json::Result<()> { Ok(()) }
+}
+
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+/// Verifies an user's email with the token that was sent to it
+pub struct ConfirmEmailVerification {
+	/// The token from the verification email
+	pub token: String,
+	/// The user whose email is being verified
+	pub user_id: crate::id::User,
+}
+
+impl Queryable<NoAuthentication, ()> for ConfirmEmailVerification {
+	fn url(&self, _: &NoAuthentication) -> String {
+		format!(
+			"{}/users/{}/verifyEmail",
+			crate::HTTP_BASE_URI,
+			self.user_id.as_ref()
+		)
+	}
+
+	fn body(
+		&self, _state: &NoAuthentication,
+	) -> Option<serde_json::Result<Vec<u8>>> {
+		Some(serde_json::to_vec(self))
+	}
+
+	fn method(&self, _: &NoAuthentication) -> racal::RequestMethod {
+		racal::RequestMethod::Post
+	}
+
+	fn deserialize(&self, _data: &[u8]) -> serde_json::Result<()> { Ok(()) }
+}
+
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+/// Requests a password reset token to be sent to the account's email
+pub struct RequestPasswordReset {
+	/// The email address of the account
+	pub email: String,
+}
+
+impl Queryable<NoAuthentication, ()> for RequestPasswordReset {
+	fn url(&self, _: &NoAuthentication) -> String {
+		format!("{}/users/requestLostPassword", crate::HTTP_BASE_URI)
+	}
+
+	fn body(
+		&self, _state: &NoAuthentication,
+	) -> Option<serde_json::Result<Vec<u8>>> {
+		Some(serde_json::to_vec(self))
+	}
+
+	fn method(&self, _: &NoAuthentication) -> racal::RequestMethod {
+		racal::RequestMethod::Post
+	}
+
+	fn deserialize(&self, _data: &[u8]) -> serde_json::Result<()> { Ok(()) }
+}
+
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+/// Sets a new password with a token from a password reset email
+pub struct ResetPassword {
+	/// The email address of the account
+	pub email: String,
+	/// The new password
+	pub new_password: String,
+	/// The token from the password reset email
+	pub token: String,
+}
+
+impl Queryable<NoAuthentication, ()> for ResetPassword {
+	fn url(&self, _: &NoAuthentication) -> String {
+		format!("{}/users/resetLostPassword", crate::HTTP_BASE_URI)
+	}
+
+	fn body(
+		&self, _state: &NoAuthentication,
+	) -> Option<serde_json::Result<Vec<u8>>> {
+		Some(serde_json::to_vec(self))
+	}
+
+	fn method(&self, _: &NoAuthentication) -> racal::RequestMethod {
+		racal::RequestMethod::Post
+	}
+
+	fn deserialize(&self, _data: &[u8]) -> serde_json::Result<()> { Ok(()) }
+}
+
+#[cfg(test)]
+#[test]
+fn register_user() {
+	let expected_string = r#"{
+  "dateOfBirth": "2000-01-01T00:00:00Z",
+  "email": "bot@example.com",
+  "password": "totally-my-password",
+  "username": "bot"
+}"#;
+	let register_query: RegisterUser =
+		serde_json::from_str(expected_string).unwrap();
+	assert_eq!(register_query.username, "bot");
+	let received_string = serde_json::to_string_pretty(&register_query).unwrap();
+
+	assert_eq!(expected_string, received_string);
+}
+
+#[cfg(test)]
+#[test]
+fn reset_password() {
+	let expected_string = r#"{
+  "email": "bot@example.com",
+  "newPassword": "totally-my-new-password",
+  "token": "totally-legit-token"
+}"#;
+	let reset_query: ResetPassword =
+		serde_json::from_str(expected_string).unwrap();
+	let received_string = serde_json::to_string_pretty(&reset_query).unwrap();
+
+	assert_eq!(expected_string, received_string);
+}
@@ -0,0 +1,56 @@
+use racal::Queryable;
+use serde::{Deserialize, Serialize};
+
+use super::NoAuthentication;
+
+/// Gets the variants that have been computed for an asset
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AssetVariants {
+	/// The hash of the asset, see [`AssetUrl::id`](crate::AssetUrl::id)
+	pub asset_hash: String,
+}
+
+impl AssetVariants {
+	/// Creates a new asset variants query
+	pub fn new(asset_hash: impl Into<String>) -> Self {
+		Self { asset_hash: asset_hash.into() }
+	}
+}
+
+impl Queryable<NoAuthentication, Vec<crate::model::AssetVariant>>
+	for AssetVariants
+{
+	fn url(&self, _: &NoAuthentication) -> String {
+		format!("{}/assets/{}/variants", crate::HTTP_BASE_URI, self.asset_hash)
+	}
+}
+
+/// Gets the metadata that has been computed for an asset
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AssetMetadata {
+	/// The hash of the asset, see [`AssetUrl::id`](crate::AssetUrl::id)
+	pub asset_hash: String,
+}
+
+impl AssetMetadata {
+	/// Creates a new asset metadata query
+	pub fn new(asset_hash: impl Into<String>) -> Self {
+		Self { asset_hash: asset_hash.into() }
+	}
+}
+
+impl Queryable<NoAuthentication, crate::model::AssetMetadata>
+	for AssetMetadata
+{
+	fn url(&self, _: &NoAuthentication) -> String {
+		format!("{}/assets/{}/metadata", crate::HTTP_BASE_URI, self.asset_hash)
+	}
+}
@@ -0,0 +1,148 @@
+use racal::Queryable;
+use serde::{Deserialize, Serialize};
+
+use super::Authentication;
+use crate::model::CloudVariablePath;
+
+/// Gets the value of a cloud variable for an user or group
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CloudVariable {
+	/// The user or group whose value to get
+	pub owner_id: crate::id::Owner,
+	/// The path of the variable
+	pub path: CloudVariablePath,
+}
+
+impl CloudVariable {
+	/// Creates a new cloud variable query
+	pub fn new(
+		owner_id: impl Into<crate::id::Owner>, path: CloudVariablePath,
+	) -> Self {
+		Self { owner_id: owner_id.into(), path }
+	}
+}
+
+impl Queryable<Authentication, crate::model::CloudVariable> for CloudVariable {
+	fn url(&self, _: &Authentication) -> String {
+		format!("{}/vars/{}", super::owner_url(&self.owner_id), self.path)
+	}
+}
+
+/// Lists the values of all the cloud variables of an user or group
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CloudVariables {
+	/// The user or group whose values to list
+	pub owner_id: crate::id::Owner,
+}
+
+impl CloudVariables {
+	/// Creates a new cloud variables listing query
+	pub fn new(owner_id: impl Into<crate::id::Owner>) -> Self {
+		Self { owner_id: owner_id.into() }
+	}
+}
+
+impl Queryable<Authentication, Vec<crate::model::CloudVariable>>
+	for CloudVariables
+{
+	fn url(&self, _: &Authentication) -> String {
+		format!("{}/vars", super::owner_url(&self.owner_id))
+	}
+}
+
+/// Sets the value of a cloud variable
+impl Queryable<Authentication, ()> for crate::model::CloudVariable {
+	fn url(&self, _: &Authentication) -> String {
+		format!("{}/vars/{}", super::owner_url(&self.owner_id), self.path)
+	}
+
+	fn body(
+		&self, _state: &Authentication,
+	) -> Option<serde_json::Result<Vec<u8>>> {
+		Some(serde_json::to_vec(self))
+	}
+
+	fn method(&self, _state: &Authentication) -> racal::RequestMethod {
+		racal::RequestMethod::Put
+	}
+
+	fn deserialize(&self, _data: &[u8]) -> serde_json::Result<()> { Ok(()) }
+}
+
+/// Gets the definition of a cloud variable, which contains its type
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CloudVariableDefinition {
+	/// The path of the variable
+	pub path: CloudVariablePath,
+}
+
+impl CloudVariableDefinition {
+	#[must_use]
+	/// Creates a new cloud variable definition query
+	pub const fn new(path: CloudVariablePath) -> Self { Self { path } }
+}
+
+impl Queryable<Authentication, crate::model::CloudVariableDefinition>
+	for CloudVariableDefinition
+{
+	fn url(&self, _: &Authentication) -> String {
+		format!(
+			"{}/vardefs/{}",
+			super::owner_url(&self.path.definition_owner_id),
+			self.path.subpath
+		)
+	}
+}
+
+#[cfg(test)]
+#[test]
+fn cloud_variable_urls() {
+	let auth = Authentication {
+		token: String::new(),
+		user_id: crate::id::User::try_from("U-auth").unwrap(),
+	};
+	let path = CloudVariablePath::try_from("G-Resonite.test.counter").unwrap();
+	let user = crate::id::User::try_from("U-user").unwrap();
+	let group = crate::id::Group::try_from("G-group").unwrap();
+	let machine = crate::id::Machine::try_from("M-machine").unwrap();
+
+	assert_eq!(
+		CloudVariable::new(user, path.clone()).url(&auth),
+		"https://api.resonite.com/users/U-user/vars/G-Resonite.test.counter"
+	);
+	assert_eq!(
+		CloudVariable::new(group.clone(), path.clone()).url(&auth),
+		"https://api.resonite.com/groups/G-group/vars/G-Resonite.test.counter"
+	);
+	assert_eq!(
+		CloudVariables::new(machine).url(&auth),
+		"https://api.resonite.com/machines/M-machine/vars"
+	);
+	assert_eq!(
+		CloudVariableDefinition::new(path.clone()).url(&auth),
+		"https://api.resonite.com/groups/G-Resonite/vardefs/test.counter"
+	);
+
+	let value = crate::model::CloudVariable::new(
+		group,
+		path,
+		&crate::model::CloudVariableValue::Int(1),
+	);
+	assert_eq!(
+		value.url(&auth),
+		"https://api.resonite.com/groups/G-group/vars/G-Resonite.test.counter"
+	);
+	assert!(matches!(value.method(&auth), racal::RequestMethod::Put));
+}
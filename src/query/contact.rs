@@ -1,12 +1,159 @@
 use racal::Queryable;
+use serde::{Deserialize, Serialize};
 
 use super::Authentication;
+use crate::model::{Contact, ContactStatus};
 
 /// Get the contacts/friends for a specific user
 pub struct Contacts;
 
-impl Queryable<Authentication, Vec<crate::model::Contact>> for Contacts {
+impl Queryable<Authentication, Vec<Contact>> for Contacts {
 	fn url(&self, auth: &Authentication) -> String {
 		format!("{}/users/{}/contacts", crate::HTTP_BASE_URI, auth.user_id.as_ref())
 	}
 }
+
+#[repr(u8)]
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "borsh", borsh(use_discriminant = true))]
+#[derive(
+	Debug,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	Hash,
+	Serialize,
+	Deserialize,
+	strum::Display,
+	strum::AsRefStr,
+	strum::VariantNames,
+)]
+/// A change to the relationship with a contact
+pub enum ContactAction {
+	/// Accepts a received contact request
+	Accept = 1,
+	/// Sends a contact request
+	Add = 0,
+	/// Blocks the user
+	Block = 3,
+	/// Ignores a received contact request
+	Ignore = 2,
+	/// Removes the contact
+	Remove = 5,
+	/// Unblocks the user, leaving them as a non-contact
+	Unblock = 4,
+}
+
+impl ContactAction {
+	#[must_use]
+	/// If the contact is accepted after the action
+	pub const fn is_accepted(self) -> bool {
+		matches!(self, Self::Accept | Self::Add)
+	}
+
+	#[must_use]
+	/// The status that the contact will have after the action
+	pub const fn status(self) -> ContactStatus {
+		match self {
+			Self::Accept | Self::Add => ContactStatus::Accepted,
+			Self::Block => ContactStatus::Blocked,
+			Self::Ignore => ContactStatus::Ignored,
+			Self::Remove | Self::Unblock => ContactStatus::None,
+		}
+	}
+}
+
+/// Changes the contact/friend status with an user.
+///
+/// Resonite handles everything from friend requests to blocking by updating
+/// the contact record of the logged in user.
+/// Returns the contact record as the API stored it.
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct UpdateContact {
+	/// The action that was applied to the contact
+	pub action: ContactAction,
+	/// The updated contact record
+	pub contact: Contact,
+}
+
+impl UpdateContact {
+	#[must_use]
+	/// Creates a query for a new contact, such as for sending a contact request
+	/// or blocking someone who isn't a contact yet.
+	pub fn create(
+		owner_id: crate::id::User, contact_id: crate::id::User,
+		username: impl Into<String>, action: ContactAction,
+	) -> Self {
+		let contact = Contact {
+			alternate_usernames: Vec::new(),
+			contact_status: ContactStatus::None,
+			id: contact_id,
+			is_accepted: false,
+			is_counterpart_migrated: false,
+			is_migrated: false,
+			latest_message_time: None,
+			owner_id,
+			profile: None,
+			username: username.into(),
+		};
+
+		Self::new(contact, action)
+	}
+
+	#[must_use]
+	/// Creates a query that applies the action to an existing contact
+	pub const fn new(mut contact: Contact, action: ContactAction) -> Self {
+		contact.contact_status = action.status();
+		contact.is_accepted = action.is_accepted();
+
+		Self { action, contact }
+	}
+}
+
+impl Queryable<Authentication, Contact> for UpdateContact {
+	fn url(&self, auth: &Authentication) -> String {
+		format!(
+			"{}/users/{}/contacts/{}",
+			crate::HTTP_BASE_URI,
+			auth.user_id.as_ref(),
+			self.contact.id.as_ref()
+		)
+	}
+
+	fn body(
+		&self, _state: &Authentication,
+	) -> Option<serde_json::Result<Vec<u8>>> {
+		Some(serde_json::to_vec(&self.contact))
+	}
+
+	fn method(&self, _state: &Authentication) -> racal::RequestMethod {
+		racal::RequestMethod::Put
+	}
+}
+
+#[cfg(test)]
+#[test]
+fn update_contact() {
+	let owner_id = crate::id::User::try_from("U-owner").unwrap();
+	let contact_id = crate::id::User::try_from("U-contact").unwrap();
+
+	let query =
+		UpdateContact::create(owner_id, contact_id, "contact", ContactAction::Add);
+	assert_eq!(query.contact.contact_status, ContactStatus::Accepted);
+	assert!(query.contact.is_accepted);
+
+	let query = UpdateContact::new(query.contact, ContactAction::Block);
+	assert_eq!(query.contact.contact_status, ContactStatus::Blocked);
+	assert!(!query.contact.is_accepted);
+
+	let query = UpdateContact::new(query.contact, ContactAction::Unblock);
+	assert_eq!(query.contact.contact_status, ContactStatus::None);
+}
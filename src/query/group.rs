@@ -1,7 +1,7 @@
 use racal::Queryable;
 use serde::{Deserialize, Serialize};
 
-use super::NoAuthentication;
+use super::{Authentication, NoAuthentication};
 
 /// Gets details of publicly listed sessions
 #[cfg_attr(
@@ -26,3 +26,106 @@ impl Queryable<NoAuthentication, crate::model::Group> for GroupInfo {
 		format!("{}/groups/{}", crate::HTTP_BASE_URI, self.group_id.as_ref())
 	}
 }
+
+/// Lists the members of a group
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GroupMembers {
+	/// The ID of the group to list the members of
+	pub group_id: crate::id::Group,
+}
+
+impl GroupMembers {
+	/// Creates a new group members query
+	pub fn new(group_id: impl Into<crate::id::Group>) -> Self {
+		Self { group_id: group_id.into() }
+	}
+}
+
+impl Queryable<Authentication, Vec<crate::model::GroupMember>>
+	for GroupMembers
+{
+	fn url(&self, _: &Authentication) -> String {
+		format!(
+			"{}/groups/{}/members",
+			crate::HTTP_BASE_URI,
+			self.group_id.as_ref()
+		)
+	}
+}
+
+/// Gets the details of a single member of a group
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GroupMember {
+	/// The ID of the group that the user is a member of
+	pub group_id: crate::id::Group,
+	/// The ID of the member
+	pub user_id: crate::id::User,
+}
+
+impl GroupMember {
+	/// Creates a new group member query
+	pub fn new(
+		group_id: impl Into<crate::id::Group>, user_id: impl Into<crate::id::User>,
+	) -> Self {
+		Self { group_id: group_id.into(), user_id: user_id.into() }
+	}
+}
+
+impl Queryable<Authentication, crate::model::GroupMember> for GroupMember {
+	fn url(&self, _: &Authentication) -> String {
+		format!(
+			"{}/groups/{}/members/{}",
+			crate::HTTP_BASE_URI,
+			self.group_id.as_ref(),
+			self.user_id.as_ref()
+		)
+	}
+}
+
+/// Lists the records of a group
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[serde_with::serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GroupRecords {
+	/// The ID of the group to list the records of
+	pub group_id: crate::id::Group,
+	#[serde_as(deserialize_as = "serde_with::DefaultOnNull")]
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	/// If to only list the records in a specific path, such as `Inventory`
+	pub path: Option<String>,
+}
+
+impl GroupRecords {
+	/// Creates a new group records query for all of the group's records
+	pub fn new(group_id: impl Into<crate::id::Group>) -> Self {
+		Self { group_id: group_id.into(), path: None }
+	}
+}
+
+impl Queryable<Authentication, Vec<crate::model::Record>> for GroupRecords {
+	fn url(&self, _: &Authentication) -> String {
+		let mut query = format!(
+			"{}/groups/{}/records",
+			crate::HTTP_BASE_URI,
+			self.group_id.as_ref()
+		);
+
+		if let Some(path) = &self.path {
+			query = query + "?path=" + &super::encode_query_value(path);
+		}
+
+		query
+	}
+}
@@ -92,3 +92,95 @@ impl Queryable<Authentication, Self> for crate::model::Message {
 		racal::RequestMethod::Post
 	}
 }
+
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+/// Marks messages of the logged in user as read
+pub struct MarkMessagesRead {
+	/// The IDs of the messages to mark as read
+	pub message_ids: Vec<String>,
+	#[cfg_attr(
+		feature = "borsh",
+		borsh(
+			serialize_with = "crate::util::borsh::time::ser",
+			deserialize_with = "crate::util::borsh::time::de"
+		)
+	)]
+	#[serde(with = "time::serde::rfc3339")]
+	/// When the messages were read, used for updating local messages
+	pub read_time: OffsetDateTime,
+}
+
+impl MarkMessagesRead {
+	/// Updates the read time of the local copies of the marked messages.
+	///
+	/// Messages that were already read keep their original read time.
+	pub fn apply(&self, messages: &mut [crate::model::Message]) {
+		for message in messages {
+			if message.read_time.is_none() && self.message_ids.contains(&message.id) {
+				message.read_time = Some(self.read_time);
+			}
+		}
+	}
+
+	/// Creates a new query for marking the messages as read now
+	pub fn new(message_ids: impl IntoIterator<Item = impl Into<String>>) -> Self {
+		Self {
+			message_ids: message_ids.into_iter().map(Into::into).collect(),
+			read_time: OffsetDateTime::now_utc(),
+		}
+	}
+}
+
+impl Queryable<Authentication, ()> for MarkMessagesRead {
+	fn url(&self, auth: &Authentication) -> String {
+		format!("{}/users/{}/messages", crate::HTTP_BASE_URI, auth.user_id.as_ref())
+	}
+
+	fn body(
+		&self, _state: &Authentication,
+	) -> Option<serde_json::Result<Vec<u8>>> {
+		Some(serde_json::to_vec(&self.message_ids))
+	}
+
+	fn method(&self, _state: &Authentication) -> racal::RequestMethod {
+		racal::RequestMethod::Patch
+	}
+
+	fn deserialize(&self, _data: &[u8]) -> serde_json::Result<()> { Ok(()) }
+}
+
+#[cfg(test)]
+#[test]
+fn mark_messages_read() {
+	let user_id = crate::id::User::try_from("U-user").unwrap();
+	let now = OffsetDateTime::now_utc();
+	let message = |id: &str, read_time| crate::model::Message {
+		content: crate::model::MessageContents::Text("Hello".to_owned()),
+		id: id.to_owned(),
+		is_migrated: false,
+		last_update_time: now,
+		owner_id: user_id.clone(),
+		read_time,
+		recipient_id: user_id.clone(),
+		send_time: now,
+		sender_id: user_id.clone(),
+		sender_user_session_id: None,
+	};
+	let earlier = now - time::Duration::hours(1);
+	let mut messages = [
+		message("MSG-1", None),
+		message("MSG-2", Some(earlier)),
+		message("MSG-3", None),
+	];
+
+	let query = MarkMessagesRead::new(["MSG-1", "MSG-2"]);
+	query.apply(&mut messages);
+
+	assert_eq!(messages[0].read_time, Some(query.read_time));
+	assert_eq!(messages[1].read_time, Some(earlier));
+	assert_eq!(messages[2].read_time, None);
+}
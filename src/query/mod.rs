@@ -9,23 +9,71 @@
 use racal::FromApiState;
 use serde::{Deserialize, Serialize};
 
+mod account;
+mod asset;
+mod cloud_variable;
 mod contact;
 mod group;
 mod message;
 mod session;
 mod stats;
+mod storage;
 mod testing;
+mod two_factor;
 mod user;
 mod user_session;
+mod user_status;
 
+pub use account::*;
+pub use asset::*;
+pub use cloud_variable::*;
 pub use contact::*;
 pub use group::*;
 pub use message::*;
 pub use session::*;
 pub use stats::*;
+pub use storage::*;
 pub use testing::*;
+pub use two_factor::*;
 pub use user::*;
 pub use user_session::*;
+pub use user_status::*;
+
+/// The base URL for the endpoints of an user, group or machine
+fn owner_url(owner: &crate::id::Owner) -> String {
+	let owner_type = match owner {
+		crate::id::Owner::Group(_) => "groups",
+		crate::id::Owner::Machine(_) => "machines",
+		crate::id::Owner::User(_) => "users",
+	};
+
+	format!("{}/{}/{}", crate::HTTP_BASE_URI, owner_type, owner.as_ref())
+}
+
+/// Percent encodes a value for use in an URL's query string
+fn encode_query_value(value: &str) -> String {
+	use std::fmt::Write;
+
+	value.bytes().fold(String::with_capacity(value.len()), |mut encoded, byte| {
+		if byte.is_ascii_alphanumeric() || b"-_.~".contains(&byte) {
+			encoded.push(byte as char);
+		} else {
+			// Writing to a string can't fail
+			let _ = write!(encoded, "%{byte:02X}");
+		}
+		encoded
+	})
+}
+
+#[cfg(test)]
+#[test]
+fn query_value_encoding() {
+	assert_eq!(
+		encode_query_value("Inventory\\My Stuff&"),
+		"Inventory%5CMy%20Stuff%26"
+	);
+	assert_eq!(encode_query_value("ä"), "%C3%A4");
+}
 
 /// Data needed to actually request an user session.
 ///
@@ -2,13 +2,167 @@ use racal::Queryable;
 use serde::{Deserialize, Serialize};
 
 use super::NoAuthentication;
+use crate::model::SessionAccessLevel;
 
 /// Gets details of publicly listed sessions
-pub struct Sessions;
+///
+/// The filters are sent to the API where it supports them, and all of them are
+/// also applied to the response client side, so that the results are
+/// consistent even if the API ignores some of them.
+/// The default query has no filters, returning everything that the API does.
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[serde_with::serde_as]
+#[derive(
+	Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize,
+)]
+pub struct Sessions {
+	#[serde_as(deserialize_as = "serde_with::DefaultOnNull")]
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	/// If to only list sessions with any of the access levels, client side
+	pub access_levels: Option<Vec<SessionAccessLevel>>,
+	#[serde_as(deserialize_as = "serde_with::DefaultOnNull")]
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	/// If to only list sessions with a specific compatibility hash
+	pub compatibility_hash: Option<String>,
+	#[serde_as(deserialize_as = "serde_with::DefaultOnNull")]
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	/// If to only list sessions hosted by a specific user
+	pub host_id: Option<crate::id::User>,
+	#[serde_as(deserialize_as = "serde_with::DefaultOnNull")]
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	/// If to only list sessions whose host's username contains the string
+	pub host_name: Option<String>,
+	#[serde_as(deserialize_as = "serde_with::DefaultOnNull")]
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	/// If to also list headless sessions without any joined users.
+	///
+	/// Left to the API's default if `None`.
+	pub include_empty_headless: Option<bool>,
+	#[serde_as(deserialize_as = "serde_with::DefaultOnNull")]
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	/// If to also list sessions that have ended.
+	///
+	/// Left to the API's default if `None`.
+	pub include_ended: Option<bool>,
+	#[serde_as(deserialize_as = "serde_with::DefaultOnNull")]
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	/// If to only list sessions with at least this many active users
+	pub min_active_users: Option<u8>,
+	#[serde_as(deserialize_as = "serde_with::DefaultOnNull")]
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	/// If to only list sessions whose name contains the string
+	pub name: Option<String>,
+	#[serde_as(deserialize_as = "serde_with::DefaultOnNull")]
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	/// If to only list sessions in a specific universe
+	pub universe_id: Option<String>,
+}
+
+impl Sessions {
+	#[must_use]
+	/// If the session passes all the filters.
+	///
+	/// Text is compared case insensitively, and the name is compared without
+	/// the formatting tags.
+	pub fn matches(&self, session: &crate::model::SessionInfo) -> bool {
+		fn contains(haystack: &str, needle: &str) -> bool {
+			haystack.to_lowercase().contains(&needle.to_lowercase())
+		}
+
+		self
+			.access_levels
+			.as_ref()
+			.is_none_or(|access_levels| access_levels.contains(&session.access_level))
+			&& self.compatibility_hash.as_ref().is_none_or(|compatibility_hash| {
+				&session.compatibility_hash == compatibility_hash
+			}) && self
+			.host_id
+			.as_ref()
+			.is_none_or(|host_id| session.host_id.as_ref() == Some(host_id))
+			&& self
+				.host_name
+				.as_ref()
+				.is_none_or(|host_name| contains(&session.host_username, host_name))
+			&& self.include_empty_headless.is_none_or(|include_empty_headless| {
+				include_empty_headless
+					|| !session.is_headless_host
+					|| session.joined_users > 0
+			}) && self
+			.include_ended
+			.is_none_or(|include_ended| include_ended || !session.has_ended)
+			&& self
+				.min_active_users
+				.is_none_or(|min_active_users| session.active_users >= min_active_users)
+			&& self
+				.name
+				.as_ref()
+				.is_none_or(|name| contains(&session.stripped_name(), name))
+			&& self
+				.universe_id
+				.as_ref()
+				.is_none_or(|universe_id| &session.universe_id == universe_id)
+	}
+}
 
 impl Queryable<NoAuthentication, Vec<crate::model::SessionInfo>> for Sessions {
 	fn url(&self, _: &NoAuthentication) -> String {
-		format!("{}/sessions", crate::HTTP_BASE_URI)
+		use super::encode_query_value as encode;
+
+		let mut params = Vec::new();
+		if let Some(compatibility_hash) = &self.compatibility_hash {
+			params
+				.push("compatibilityHash=".to_owned() + &encode(compatibility_hash));
+		}
+		if let Some(host_id) = &self.host_id {
+			params.push("hostId=".to_owned() + &encode(host_id.as_ref()));
+		}
+		if let Some(host_name) = &self.host_name {
+			params.push("hostName=".to_owned() + &encode(host_name));
+		}
+		if let Some(include_empty_headless) = self.include_empty_headless {
+			params.push(format!("includeEmptyHeadless={include_empty_headless}"));
+		}
+		if let Some(include_ended) = self.include_ended {
+			params.push(format!("includeEnded={include_ended}"));
+		}
+		if let Some(min_active_users) = self.min_active_users {
+			params.push("minActiveUsers=".to_owned() + &min_active_users.to_string());
+		}
+		if let Some(name) = &self.name {
+			params.push("name=".to_owned() + &encode(name));
+		}
+		if let Some(universe_id) = &self.universe_id {
+			params.push("universeId=".to_owned() + &encode(universe_id));
+		}
+
+		let mut query = format!("{}/sessions", crate::HTTP_BASE_URI);
+		if !params.is_empty() {
+			query = query + "?" + &params.join("&");
+		}
+
+		query
+	}
+
+	fn deserialize(
+		&self, data: &[u8],
+	) -> serde_json::Result<Vec<crate::model::SessionInfo>> {
+		let mut sessions: Vec<crate::model::SessionInfo> =
+			serde_json::from_slice(data)?;
+		sessions.retain(|session| self.matches(session));
+
+		Ok(sessions)
 	}
 }
 
@@ -35,3 +189,149 @@ impl Queryable<NoAuthentication, crate::model::SessionInfo> for SessionInfo {
 		format!("{}/sessions/{}", crate::HTTP_BASE_URI, self.session_id.as_ref())
 	}
 }
+
+#[cfg(test)]
+#[test]
+fn sessions_query() {
+	let query = Sessions::default();
+	assert_eq!(
+		query.url(&NoAuthentication {}),
+		"https://api.resonite.com/sessions"
+	);
+
+	let query = Sessions {
+		access_levels: Some(vec![SessionAccessLevel::Anyone]),
+		host_name: Some("toaster".to_owned()),
+		include_ended: Some(true),
+		min_active_users: Some(1),
+		name: Some("Soko's Library".to_owned()),
+		..Sessions::default()
+	};
+	assert_eq!(
+		query.url(&NoAuthentication {}),
+		"https://api.resonite.com/sessions?hostName=toaster&includeEnded=true&minActiveUsers=1&name=Soko%27s%20Library"
+	);
+
+	let json = serde_json::json!([{
+		"name": "<color=yellow>Soko's Library</color>",
+		"sessionId": "S-U-1QMVJqtmCsC:soko_library",
+		"normalizedSessionId": "s-u-1qmvjqtmcsc:soko_library",
+		"hostMachineId": "7rpx1gn4dojdinqdhhwddqmbwqq1dw1jjdzjstpm3yisss5poq5o",
+		"hostUsername": "Toaster_headless",
+		"appVersion": "2024.7.25.1284",
+		"headlessHost": true,
+		"sessionURLs": [],
+		"sessionUsers": [],
+		"joinedUsers": 1,
+		"activeUsers": 1,
+		"totalJoinedUsers": 1,
+		"totalActiveUsers": 1,
+		"maxUsers": 10,
+		"mobileFriendly": false,
+		"sessionBeginTime": "2024-07-28T22:00:44.403632Z",
+		"lastUpdate": "2024-07-28T23:25:47.013536Z",
+		"accessLevel": "Anyone",
+	}]);
+	let data = serde_json::to_vec(&json).unwrap();
+	assert_eq!(query.deserialize(&data).unwrap().len(), 1);
+
+	let query = Sessions { min_active_users: Some(2), ..query };
+	assert!(query.deserialize(&data).unwrap().is_empty());
+	let query = Sessions {
+		access_levels: Some(vec![SessionAccessLevel::Private]),
+		min_active_users: None,
+		..query
+	};
+	assert!(query.deserialize(&data).unwrap().is_empty());
+}
+
+#[cfg(test)]
+#[test]
+fn sessions_filters() {
+	let session: crate::model::SessionInfo =
+		serde_json::from_value(serde_json::json!({
+			"name": "<b>Test</b> World",
+			"sessionId": "S-U-host:test",
+			"normalizedSessionId": "s-u-host:test",
+			"hostUserId": "U-host",
+			"hostMachineId": "machine",
+			"hostUsername": "Host",
+			"compatibilityHash": "hash",
+			"universeId": "universe",
+			"appVersion": "2024.7.25.1284",
+			"headlessHost": true,
+			"hasEnded": false,
+			"sessionURLs": [],
+			"sessionUsers": [],
+			"joinedUsers": 0,
+			"activeUsers": 0,
+			"totalJoinedUsers": 0,
+			"totalActiveUsers": 0,
+			"maxUsers": 10,
+			"mobileFriendly": false,
+			"sessionBeginTime": "2024-07-28T22:00:44.403632Z",
+			"lastUpdate": "2024-07-28T23:25:47.013536Z",
+			"accessLevel": "Anyone",
+		}))
+		.unwrap();
+	let base = Sessions::default();
+	assert!(base.matches(&session));
+
+	let cases = [
+		(
+			Sessions {
+				access_levels: Some(vec![SessionAccessLevel::Anyone]),
+				..base.clone()
+			},
+			Sessions {
+				access_levels: Some(vec![SessionAccessLevel::Private]),
+				..base.clone()
+			},
+		),
+		(
+			Sessions { compatibility_hash: Some("hash".to_owned()), ..base.clone() },
+			Sessions { compatibility_hash: Some("other".to_owned()), ..base.clone() },
+		),
+		(
+			Sessions {
+				host_id: Some(crate::id::User::try_from("U-host").unwrap()),
+				..base.clone()
+			},
+			Sessions {
+				host_id: Some(crate::id::User::try_from("U-other").unwrap()),
+				..base.clone()
+			},
+		),
+		(
+			Sessions { host_name: Some("hOS".to_owned()), ..base.clone() },
+			Sessions { host_name: Some("other".to_owned()), ..base.clone() },
+		),
+		(
+			Sessions { include_empty_headless: Some(true), ..base.clone() },
+			Sessions { include_empty_headless: Some(false), ..base.clone() },
+		),
+		(
+			Sessions { min_active_users: Some(0), ..base.clone() },
+			Sessions { min_active_users: Some(1), ..base.clone() },
+		),
+		(
+			Sessions { name: Some("test world".to_owned()), ..base.clone() },
+			Sessions { name: Some("<b>".to_owned()), ..base.clone() },
+		),
+		(
+			Sessions { universe_id: Some("universe".to_owned()), ..base.clone() },
+			Sessions { universe_id: Some("other".to_owned()), ..base.clone() },
+		),
+	];
+	for (matching, other) in cases {
+		assert!(matching.matches(&session), "{matching:?}");
+		assert!(!other.matches(&session), "{other:?}");
+	}
+
+	let ended = crate::model::SessionInfo { has_ended: true, ..session };
+	assert!(base.matches(&ended));
+	assert!(
+		Sessions { include_ended: Some(true), ..base.clone() }.matches(&ended)
+	);
+	assert!(!Sessions { include_ended: Some(false), ..base }.matches(&ended));
+}
@@ -24,7 +24,10 @@ impl Queryable<NoAuthentication, crate::model::CloudStatistics>
 	}
 }
 
-/// Gets statistics related to the cloud
+/// Notifies the API that an instance of Resonite on the machine is online.
+///
+/// Headless hosts should send this periodically, which the `InstanceHeartbeat`
+/// helper of the `http_client` feature can be used for.
 pub struct NotifyInstanceOnline(pub crate::id::Machine);
 
 impl Queryable<NoAuthentication, ()> for NotifyInstanceOnline {
@@ -35,4 +38,6 @@ impl Queryable<NoAuthentication, ()> for NotifyInstanceOnline {
 	fn method(&self, _state: &NoAuthentication) -> RequestMethod {
 		RequestMethod::Post
 	}
+
+	fn deserialize(&self, _data: &[u8]) -> serde_json::Result<()> { Ok(()) }
 }
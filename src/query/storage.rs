@@ -0,0 +1,28 @@
+use racal::Queryable;
+use serde::{Deserialize, Serialize};
+
+use super::Authentication;
+
+/// Gets the storage quota and usage of an user or group
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Storage {
+	/// The user or group whose storage to get
+	pub owner_id: crate::id::Owner,
+}
+
+impl Storage {
+	/// Creates a new storage query
+	pub fn new(owner_id: impl Into<crate::id::Owner>) -> Self {
+		Self { owner_id: owner_id.into() }
+	}
+}
+
+impl Queryable<Authentication, crate::model::Storage> for Storage {
+	fn url(&self, _: &Authentication) -> String {
+		format!("{}/storage", super::owner_url(&self.owner_id))
+	}
+}
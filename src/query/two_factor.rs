@@ -0,0 +1,137 @@
+use racal::Queryable;
+use serde::{Deserialize, Serialize};
+
+use super::Authentication;
+
+/// Starts enrolling the logged in user to TOTP two factor authentication.
+///
+/// Needs to be followed by [`VerifyTotp`] for it to be enabled.
+pub struct EnrollTotp;
+
+impl Queryable<Authentication, crate::model::TotpEnrollment> for EnrollTotp {
+	fn url(&self, auth: &Authentication) -> String {
+		format!("{}/users/{}/totp", crate::HTTP_BASE_URI, auth.user_id.as_ref())
+	}
+
+	fn method(&self, _: &Authentication) -> racal::RequestMethod {
+		racal::RequestMethod::Post
+	}
+}
+
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+/// Verifies a TOTP code, which finishes enrollment if it's in progress
+pub struct VerifyTotp {
+	/// The current TOTP code
+	pub code: String,
+}
+
+impl Queryable<Authentication, ()> for VerifyTotp {
+	fn url(&self, auth: &Authentication) -> String {
+		format!(
+			"{}/users/{}/totp/verify",
+			crate::HTTP_BASE_URI,
+			auth.user_id.as_ref()
+		)
+	}
+
+	fn body(
+		&self, _state: &Authentication,
+	) -> Option<serde_json::Result<Vec<u8>>> {
+		Some(serde_json::to_vec(self))
+	}
+
+	fn method(&self, _: &Authentication) -> racal::RequestMethod {
+		racal::RequestMethod::Post
+	}
+
+	fn deserialize(&self, _data: &[u8]) -> serde_json::Result<()> { Ok(()) }
+}
+
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+/// Disables two factor authentication for the logged in user
+pub struct DisableTotp {
+	/// The current TOTP code or a recovery code
+	pub code: String,
+}
+
+impl Queryable<Authentication, ()> for DisableTotp {
+	fn url(&self, auth: &Authentication) -> String {
+		format!("{}/users/{}/totp", crate::HTTP_BASE_URI, auth.user_id.as_ref())
+	}
+
+	fn body(
+		&self, _state: &Authentication,
+	) -> Option<serde_json::Result<Vec<u8>>> {
+		Some(serde_json::to_vec(self))
+	}
+
+	fn method(&self, _: &Authentication) -> racal::RequestMethod {
+		racal::RequestMethod::Delete
+	}
+
+	fn deserialize(&self, _data: &[u8]) -> serde_json::Result<()> { Ok(()) }
+}
+
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+/// Generates new recovery codes, invalidating the old ones
+pub struct RegenerateRecoveryCodes {
+	/// The current TOTP code
+	pub code: String,
+}
+
+impl Queryable<Authentication, crate::model::RecoveryCodes>
+	for RegenerateRecoveryCodes
+{
+	fn url(&self, auth: &Authentication) -> String {
+		format!(
+			"{}/users/{}/totp/recoveryCodes",
+			crate::HTTP_BASE_URI,
+			auth.user_id.as_ref()
+		)
+	}
+
+	fn body(
+		&self, _state: &Authentication,
+	) -> Option<serde_json::Result<Vec<u8>>> {
+		Some(serde_json::to_vec(self))
+	}
+
+	fn method(&self, _: &Authentication) -> racal::RequestMethod {
+		racal::RequestMethod::Post
+	}
+}
+
+#[cfg(test)]
+#[test]
+fn totp_enrollment() {
+	let expected_string = r#"{
+  "recoveryCodes": [
+    "aaaa-bbbb",
+    "cccc-dddd"
+  ],
+  "secret": "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ",
+  "uri": "otpauth://totp/Resonite:bot?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ"
+}"#;
+	let enrollment: crate::model::TotpEnrollment =
+		serde_json::from_str(expected_string).unwrap();
+	assert_eq!(enrollment.recovery_codes.len(), 2);
+	assert_eq!(
+		serde_json::to_string_pretty(&enrollment).unwrap(),
+		expected_string
+	);
+}
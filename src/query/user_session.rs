@@ -248,3 +248,58 @@ impl Queryable<Authentication, ()> for ExtendUserSession {
 
 	fn deserialize(&self, _data: &[u8]) -> serde_json::Result<()> { Ok(()) }
 }
+
+/// Lists all the active user sessions of the logged in user.
+///
+/// Useful for auditing on which machines the account is logged in on.
+pub struct UserSessions;
+
+impl Queryable<Authentication, Vec<crate::model::UserSessionInfo>>
+	for UserSessions
+{
+	fn url(&self, auth: &Authentication) -> String {
+		format!(
+			"{}/users/{}/userSessions",
+			crate::HTTP_BASE_URI,
+			auth.user_id.as_ref()
+		)
+	}
+}
+
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Deserialize, Serialize)]
+/// Revokes an user session, logging it out.
+///
+/// Revoking the user session that's being used for authentication ends it
+/// too, and thus the authentication can't be used afterwards.
+pub struct RevokeUserSession {
+	/// The ID of the user session to revoke, from
+	/// [`UserSessionInfo`](crate::model::UserSessionInfo::id)
+	pub id: crate::id::UserSession,
+}
+
+impl RevokeUserSession {
+	#[must_use]
+	/// Creates a query that revokes the user session
+	pub const fn new(id: crate::id::UserSession) -> Self { Self { id } }
+}
+
+impl Queryable<Authentication, ()> for RevokeUserSession {
+	fn url(&self, auth: &Authentication) -> String {
+		format!(
+			"{}/users/{}/userSessions/{}",
+			crate::HTTP_BASE_URI,
+			auth.user_id.as_ref(),
+			self.id.as_ref()
+		)
+	}
+
+	fn method(&self, _: &Authentication) -> racal::RequestMethod {
+		racal::RequestMethod::Delete
+	}
+
+	fn deserialize(&self, _data: &[u8]) -> serde_json::Result<()> { Ok(()) }
+}
@@ -0,0 +1,47 @@
+use racal::Queryable;
+use serde::{Deserialize, Serialize};
+
+use super::Authentication;
+
+/// Gets the current status of an user
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct UserStatus {
+	/// The ID of the user to query the status of
+	pub user_id: crate::id::User,
+}
+
+impl UserStatus {
+	/// Creates a new user status query
+	pub fn new(user_id: impl Into<crate::id::User>) -> Self {
+		Self { user_id: user_id.into() }
+	}
+}
+
+impl Queryable<Authentication, crate::model::UserStatus> for UserStatus {
+	fn url(&self, _: &Authentication) -> String {
+		format!("{}/users/{}/status", crate::HTTP_BASE_URI, self.user_id.as_ref())
+	}
+}
+
+/// Publish the status of the logged in user
+impl Queryable<Authentication, ()> for crate::model::UserStatus {
+	fn url(&self, auth: &Authentication) -> String {
+		format!("{}/users/{}/status", crate::HTTP_BASE_URI, auth.user_id.as_ref())
+	}
+
+	fn body(
+		&self, _state: &Authentication,
+	) -> Option<serde_json::Result<Vec<u8>>> {
+		Some(serde_json::to_vec(self))
+	}
+
+	fn method(&self, _state: &Authentication) -> racal::RequestMethod {
+		racal::RequestMethod::Put
+	}
+
+	fn deserialize(&self, _data: &[u8]) -> serde_json::Result<()> { Ok(()) }
+}
@@ -21,8 +21,6 @@ use time::OffsetDateTime;
 	Debug,
 	Clone,
 	PartialEq,
-	Eq,
-	Hash,
 	Serialize,
 	Deserialize,
 	strum::Display,
@@ -46,22 +44,23 @@ pub enum Message {
 		#[serde(rename = "type")]
 		/// A hack to force serde to have this as `"type":7`
 		num: VariantNumber<7>,
+		#[serde(rename = "allowReconnect")]
+		#[serde(default)]
+		/// If the client is allowed to reconnect
+		allow_reconnect: bool,
+		#[serde(skip_serializing_if = "Option::is_none")]
+		#[serde(default)]
+		/// Why the connection was closed, if it was because of an error
+		error: Option<String>,
 	},
 	/// Invocation completed
 	Completion {
 		#[serde(rename = "type")]
 		/// A hack to force serde to have this as `"type":3`
 		num: VariantNumber<3>,
-		// #[cfg_attr(
-		// 	feature = "borsh",
-		// 	borsh(
-		// 		serialize_with = "crate::util::borsh::json::ser",
-		// 		deserialize_with = "crate::util::borsh::json::de"
-		// 	)
-		// )]
 		#[serde(flatten)]
 		/// The data for the invocation completion
-		data: serde_json::Value,
+		data: Completion,
 	},
 	/// RPC call
 	Invocation {
@@ -83,32 +82,18 @@ pub enum Message {
 		#[serde(rename = "type")]
 		/// A hack to force serde to have this as `"type":4`
 		num: VariantNumber<4>,
-		// #[cfg_attr(
-		// 	feature = "borsh",
-		// 	borsh(
-		// 		serialize_with = "crate::util::borsh::json::ser",
-		// 		deserialize_with = "crate::util::borsh::json::de"
-		// 	)
-		// )]
 		#[serde(flatten)]
 		/// The data for the stream invocation
-		data: serde_json::Value,
+		data: StreamInvocation,
 	},
 	/// Data
 	StreamItem {
 		#[serde(rename = "type")]
 		/// A hack to force serde to have this as `"type":2`
 		num: VariantNumber<2>,
-		// #[cfg_attr(
-		// 	feature = "borsh",
-		// 	borsh(
-		// 		serialize_with = "crate::util::borsh::json::ser",
-		// 		deserialize_with = "crate::util::borsh::json::de"
-		// 	)
-		// )]
 		#[serde(flatten)]
 		/// The data for the stream item
-		data: serde_json::Value,
+		data: StreamItem,
 	},
 }
 
@@ -194,18 +179,78 @@ fn message_borsh() {
 	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
 )]
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 /// Invocation cancellation
 pub struct CancelInvocation {
 	/// The ID of the invocation
 	pub invocation_id: String,
 }
 
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[serde_with::serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// The result of an invocation
+pub struct Completion {
+	#[serde_as(deserialize_as = "serde_with::DefaultOnNull")]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	#[serde(default)]
+	/// The error that happened with the invocation, if it failed
+	pub error: Option<String>,
+	/// The ID of the invocation that completed
+	pub invocation_id: String,
+	#[cfg_attr(
+		feature = "borsh",
+		borsh(
+			serialize_with = "crate::util::borsh::json::ser",
+			deserialize_with = "crate::util::borsh::json::de"
+		)
+	)]
+	#[serde(skip_serializing_if = "serde_json::Value::is_null")]
+	#[serde(default)]
+	/// What the invocation returned, `null` if nothing
+	pub result: serde_json::Value,
+}
+
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[serde_with::serde_as]
+#[derive(
+	Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize,
+)]
+/// The response of the hub to the protocol handshake.
+///
+/// Sent before any other messages, and is empty if the handshake succeeded.
+pub struct HandshakeResponse {
+	#[serde_as(deserialize_as = "serde_with::DefaultOnNull")]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	#[serde(default)]
+	/// Why the handshake failed
+	pub error: Option<String>,
+}
+
+impl HandshakeResponse {
+	/// Turns the response into a result
+	///
+	/// # Errors
+	///
+	/// If the hub rejected the handshake
+	pub fn into_result(self) -> Result<(), String> {
+		self.error.map_or(Ok(()), Err)
+	}
+}
+
 // #[cfg_attr(
 // 	feature = "borsh",
 // 	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
 // )]
 #[serde_with::serde_as]
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 /// An invocation
 pub struct Invocation {
@@ -221,6 +266,43 @@ pub struct Invocation {
 	pub invocation_id: Option<String>,
 }
 
+// #[cfg_attr(
+// 	feature = "borsh",
+// 	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+// )]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// An invocation whose results are sent as [`StreamItem`]s, until a
+/// [`Completion`] ends the stream
+pub struct StreamInvocation {
+	/// Data of the invocation
+	#[serde(flatten)]
+	pub data: InvocationData,
+	/// The ID of the invocation, which the items are sent with
+	pub invocation_id: String,
+}
+
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// An item of a streaming invocation's results
+pub struct StreamItem {
+	/// The ID of the invocation that the item is for
+	pub invocation_id: String,
+	#[cfg_attr(
+		feature = "borsh",
+		borsh(
+			serialize_with = "crate::util::borsh::json::ser",
+			deserialize_with = "crate::util::borsh::json::de"
+		)
+	)]
+	/// The item
+	pub item: serde_json::Value,
+}
+
 #[repr(u8)]
 // #[cfg_attr(
 // 	feature = "borsh",
@@ -231,8 +313,6 @@ pub struct Invocation {
 	Debug,
 	Clone,
 	PartialEq,
-	Eq,
-	Hash,
 	Serialize,
 	Deserialize,
 	strum::Display,
@@ -242,10 +322,27 @@ pub struct Invocation {
 #[serde(tag = "target", content = "arguments")]
 /// Data of an invocation
 pub enum InvocationData {
+	/// Broadcasts the status of the logged in user, sent by the client
+	BroadcastStatus((Box<crate::model::UserStatus>, BroadcastTarget)),
+	/// A contact of the logged in user was added or its status changed,
+	/// such as when a contact request is received or accepted
+	ContactAddedOrUpdated((Box<crate::model::Contact>,)),
 	/// Debug data
 	Debug((String,)),
+	/// Starts the exchange of statuses, sent by the client
+	InitializeStatus(NoArguments),
+	/// Marks messages as read, sent by the client
+	MarkMessagesRead((MarkReadBatch,)),
+	/// Confirmation that a message the client sent was delivered
+	MessageSent((Box<crate::model::Message>,)),
+	/// Notification that the recipient read messages
+	MessagesRead((ReadMessageBatch,)),
+	/// A new message for the logged in user
+	ReceiveMessage((Box<crate::model::Message>,)),
 	/// Data about a session update
 	ReceiveSessionUpdate((Box<crate::model::SessionInfo>,)),
+	/// A status update of a contact or the logged in user
+	ReceiveStatusUpdate((Box<crate::model::UserStatus>,)),
 	/// Session removal data
 	// #[cfg_attr(
 	// 	feature = "borsh",
@@ -255,6 +352,14 @@ pub enum InvocationData {
 	// 	)
 	// )]
 	RemoveSession((crate::id::Session, OffsetDateTime)),
+	/// Requests the status of an user, or all contacts if `None`.
+	///
+	/// The second argument is if the logged in user is invisible.
+	/// Sent by the client, with the statuses arriving as
+	/// [`ReceiveStatusUpdate`](InvocationData::ReceiveStatusUpdate)s.
+	RequestStatus((Option<crate::id::User>, bool)),
+	/// Sends a message, sent by the client
+	SendMessage((Box<crate::model::Message>,)),
 	/// Not yet supported or failed serde parsing of the invocation
 	// #[cfg_attr(
 	// 	feature = "borsh",
@@ -267,6 +372,302 @@ pub enum InvocationData {
 	Unknown(serde_json::Value),
 }
 
+impl From<InvocationData> for Invocation {
+	fn from(data: InvocationData) -> Self { Self { data, invocation_id: None } }
+}
+
+impl From<Invocation> for Message {
+	fn from(data: Invocation) -> Self {
+		Self::Invocation { num: VariantNumber, data }
+	}
+}
+
+impl From<StreamInvocation> for Message {
+	fn from(data: StreamInvocation) -> Self {
+		Self::StreamInvocation { num: VariantNumber, data }
+	}
+}
+
+impl From<CancelInvocation> for Message {
+	fn from(data: CancelInvocation) -> Self {
+		Self::CancelInvocation { num: VariantNumber, data }
+	}
+}
+
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(
+	Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize,
+)]
+/// Arguments for hub methods that don't take any, serialized as `[]`
+pub struct NoArguments();
+
+#[repr(u8)]
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "borsh", borsh(use_discriminant = true))]
+#[derive(
+	Debug,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	Hash,
+	Default,
+	Serialize,
+	Deserialize,
+	strum::Display,
+	strum::EnumString,
+	strum::AsRefStr,
+	strum::VariantNames,
+)]
+/// Who a status broadcast is sent to
+pub enum BroadcastGroup {
+	#[default]
+	/// All the contacts of the user
+	AllContacts = 1,
+	/// Anyone who is listening for the user's status
+	Public = 0,
+	/// Only the contacts in the target IDs
+	SpecificContacts = 2,
+}
+
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[serde_with::serde_as]
+#[derive(
+	Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize,
+)]
+#[serde(rename_all = "camelCase")]
+/// The target of a status broadcast
+pub struct BroadcastTarget {
+	/// Who the broadcast is sent to
+	pub group: BroadcastGroup,
+	#[serde_as(deserialize_as = "serde_with::DefaultOnNull")]
+	#[serde(default)]
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	/// The contacts to send the broadcast to, for specific contacts
+	pub target_ids: Vec<crate::id::User>,
+}
+
+impl BroadcastTarget {
+	#[must_use]
+	/// A target of only the specific contacts
+	pub const fn contacts(target_ids: Vec<crate::id::User>) -> Self {
+		Self { group: BroadcastGroup::SpecificContacts, target_ids }
+	}
+
+	#[must_use]
+	/// A target of the group, which shouldn't be specific contacts
+	pub const fn group(group: BroadcastGroup) -> Self {
+		Self { group, target_ids: Vec::new() }
+	}
+}
+
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// Messages from a sender that the logged in user has read
+pub struct MarkReadBatch {
+	/// The IDs of the messages
+	pub ids: Vec<String>,
+	#[cfg_attr(
+		feature = "borsh",
+		borsh(
+			serialize_with = "crate::util::borsh::time::ser",
+			deserialize_with = "crate::util::borsh::time::de"
+		)
+	)]
+	#[serde(with = "time::serde::rfc3339")]
+	/// When the messages were read
+	pub read_time: OffsetDateTime,
+	/// The user that sent the messages
+	pub sender_id: crate::id::User,
+}
+
+impl MarkReadBatch {
+	#[must_use]
+	/// Creates a new batch, with the read time set to now
+	pub fn new(sender_id: crate::id::User, ids: Vec<String>) -> Self {
+		Self { ids, read_time: OffsetDateTime::now_utc(), sender_id }
+	}
+}
+
+#[cfg_attr(
+	feature = "borsh",
+	derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// Messages that the recipient has read
+pub struct ReadMessageBatch {
+	/// The IDs of the messages
+	pub ids: Vec<String>,
+	#[cfg_attr(
+		feature = "borsh",
+		borsh(
+			serialize_with = "crate::util::borsh::time::ser",
+			deserialize_with = "crate::util::borsh::time::de"
+		)
+	)]
+	#[serde(with = "time::serde::rfc3339")]
+	/// When the messages were read
+	pub read_time: OffsetDateTime,
+	/// The user that read the messages
+	pub recipient_id: crate::id::User,
+}
+
+#[cfg(test)]
+#[test]
+fn messaging_invocation_serde() {
+	let src = r#"{"type":1,"target":"MessagesRead","arguments":[{"ids":["MSG-a","MSG-b"],"readTime":"2024-01-01T12:00:00Z","recipientId":"U-recipient"}]}"#;
+	let msg: Message = serde_json::from_str(src).unwrap();
+	let Message::Invocation { data, .. } = &msg else {
+		panic!("Expected an invocation, got {msg:?}");
+	};
+	let InvocationData::MessagesRead((batch,)) = &data.data else {
+		panic!("Expected messages read, got {data:?}");
+	};
+	assert_eq!(batch.ids, vec!["MSG-a".to_owned(), "MSG-b".to_owned()]);
+	assert_eq!(serde_json::to_string(&msg).unwrap(), src);
+
+	let message: crate::model::Message =
+		serde_json::from_value(serde_json::json!({
+			"id": "MSG-a",
+			"ownerId": "U-owner",
+			"recipientId": "U-recipient",
+			"senderId": "U-owner",
+			"messageType": "Text",
+			"content": "Hello",
+			"sendTime": "2024-01-01T12:00:00Z",
+			"lastUpdateTime": "2024-01-01T12:00:00Z",
+		}))
+		.unwrap();
+	let msg = Message::from(Invocation::from(InvocationData::SendMessage((
+		Box::new(message),
+	))));
+	let as_json = serde_json::to_value(&msg).unwrap();
+	assert_eq!(as_json["target"], "SendMessage");
+	assert_eq!(as_json["arguments"][0]["content"], "Hello");
+	let parsed: Message = serde_json::from_value(as_json).unwrap();
+	assert_eq!(msg, parsed);
+
+	let src = r#"{"target":"ReceiveMessage","arguments":[{"id":"MSG-a","ownerId":"U-owner","recipientId":"U-recipient","senderId":"U-owner","messageType":"Text","content":"Hello","sendTime":"2024-01-01T12:00:00Z","lastUpdateTime":"2024-01-01T12:00:00Z"}]}"#;
+	let data: InvocationData = serde_json::from_str(src).unwrap();
+	assert!(matches!(data, InvocationData::ReceiveMessage(_)));
+}
+
+#[cfg(test)]
+#[test]
+fn status_invocation_serde() {
+	let src = r#"{"target":"InitializeStatus","arguments":[]}"#;
+	let data: InvocationData = serde_json::from_str(src).unwrap();
+	assert_eq!(data, InvocationData::InitializeStatus(NoArguments()));
+	assert_eq!(serde_json::to_string(&data).unwrap(), src);
+
+	let src = r#"{"target":"RequestStatus","arguments":[null,false]}"#;
+	let data: InvocationData = serde_json::from_str(src).unwrap();
+	assert_eq!(data, InvocationData::RequestStatus((None, false)));
+	assert_eq!(serde_json::to_string(&data).unwrap(), src);
+
+	let user_id = crate::id::User::try_from("U-bot").unwrap();
+	let status = crate::model::UserStatus::new(
+		user_id.clone(),
+		crate::model::OnlineStatus::Online,
+		crate::model::UserSessionType::Bot,
+	);
+	let data = InvocationData::BroadcastStatus((
+		Box::new(status),
+		BroadcastTarget::contacts(vec![user_id]),
+	));
+	let as_json = serde_json::to_value(&data).unwrap();
+	assert_eq!(as_json["arguments"][1]["group"], "SpecificContacts");
+	assert_eq!(as_json["arguments"][1]["targetIds"][0], "U-bot");
+	let parsed: InvocationData = serde_json::from_value(as_json).unwrap();
+	assert_eq!(data, parsed);
+
+	let InvocationData::BroadcastStatus((status, _)) = data else {
+		unreachable!();
+	};
+	let data = InvocationData::ReceiveStatusUpdate((status,));
+	let as_json = serde_json::to_string(&data).unwrap();
+	assert_eq!(data, serde_json::from_str(&as_json).unwrap());
+}
+
+#[cfg(test)]
+#[test]
+fn contact_invocation_serde() {
+	let src = r#"{"type":1,"target":"ContactAddedOrUpdated","arguments":[{"id":"U-contact","contactUsername":"contact","contactStatus":"Requested","isAccepted":false,"ownerId":"U-owner"}]}"#;
+	let msg: Message = serde_json::from_str(src).unwrap();
+	let Message::Invocation { data, .. } = msg else {
+		panic!("Expected an invocation, got {msg:?}");
+	};
+	let InvocationData::ContactAddedOrUpdated((contact,)) = data.data else {
+		panic!("Expected a contact update, got {data:?}");
+	};
+	assert_eq!(contact.id.as_ref(), "U-contact");
+	assert_eq!(contact.contact_status, crate::model::ContactStatus::Requested);
+}
+
+#[cfg(test)]
+#[test]
+fn completion_serde() {
+	let src = r#"{"type":3,"invocationId":"1","result":{"a":1}}"#;
+	let msg: Message = serde_json::from_str(src).unwrap();
+	let Message::Completion { data, .. } = &msg else {
+		panic!("Expected a completion, got {msg:?}");
+	};
+	assert_eq!(data.invocation_id, "1");
+	assert_eq!(data.result, serde_json::json!({ "a": 1 }));
+	assert_eq!(serde_json::to_string(&msg).unwrap(), src);
+
+	let src = r#"{"type":3,"error":"Failed","invocationId":"2"}"#;
+	let msg: Message = serde_json::from_str(src).unwrap();
+	let Message::Completion { data, .. } = &msg else {
+		panic!("Expected a completion, got {msg:?}");
+	};
+	assert_eq!(data.error.as_deref(), Some("Failed"));
+	assert!(data.result.is_null());
+	assert_eq!(serde_json::to_string(&msg).unwrap(), src);
+}
+
+#[cfg(test)]
+#[test]
+fn stream_serde() {
+	let msg = Message::from(StreamInvocation {
+		data: InvocationData::Debug(("Test".to_owned(),)),
+		invocation_id: "1".to_owned(),
+	});
+	assert_eq!(
+		serde_json::to_string(&msg).unwrap(),
+		r#"{"type":4,"target":"Debug","arguments":["Test"],"invocationId":"1"}"#
+	);
+
+	let src = r#"{"type":2,"invocationId":"1","item":{"a":1}}"#;
+	let msg: Message = serde_json::from_str(src).unwrap();
+	let Message::StreamItem { data, .. } = &msg else {
+		panic!("Expected a stream item, got {msg:?}");
+	};
+	assert_eq!(data.invocation_id, "1");
+	assert_eq!(data.item, serde_json::json!({ "a": 1 }));
+	assert_eq!(serde_json::to_string(&msg).unwrap(), src);
+
+	let msg = Message::from(CancelInvocation { invocation_id: "1".to_owned() });
+	assert_eq!(
+		serde_json::to_string(&msg).unwrap(),
+		r#"{"type":5,"invocationId":"1"}"#
+	);
+}
+
 #[cfg(test)]
 #[test]
 fn invocation_data_serde() {
@@ -326,3 +727,30 @@ fn invocation_data_serde() {
 	let parsed = serde_json::from_str(&as_json).unwrap();
 	assert_eq!(msg, parsed);
 }
+
+#[cfg(test)]
+#[test]
+fn handshake_and_close_serde() {
+	let response: HandshakeResponse = serde_json::from_str("{}").unwrap();
+	assert_eq!(response.into_result(), Ok(()));
+	let response: HandshakeResponse = serde_json::from_str(
+		r#"{"error":"Requested protocol 'messagepack' is not available."}"#,
+	)
+	.unwrap();
+	assert!(response.into_result().is_err());
+
+	let msg: Message = serde_json::from_str(r#"{"type":7}"#).unwrap();
+	assert_eq!(
+		msg,
+		Message::Close { num: VariantNumber, allow_reconnect: false, error: None }
+	);
+	let msg: Message = serde_json::from_str(
+		r#"{"type":7,"error":"Server is shutting down","allowReconnect":true}"#,
+	)
+	.unwrap();
+	let Message::Close { allow_reconnect, error, .. } = msg else {
+		panic!("should be a close message");
+	};
+	assert!(allow_reconnect);
+	assert_eq!(error.as_deref(), Some("Server is shutting down"));
+}
@@ -416,3 +416,135 @@ pub mod borsh {
 		}
 	}
 }
+
+/// Generating time based one time passwords for two factor authentication
+#[cfg(feature = "totp_util")]
+pub mod totp {
+	use hmac::{Hmac, Mac};
+	use sha1::Sha1;
+
+	/// How many seconds a single code is valid for
+	pub const PERIOD_SECONDS: u64 = 30;
+
+	/// How many digits the codes have
+	pub const DIGITS: u32 = 6;
+
+	/// Decodes a base32 (RFC 4648) encoded secret, as used in `otpauth://` URIs.
+	///
+	/// Padding, spaces and letter case are ignored.
+	///
+	/// # Errors
+	///
+	/// If the secret contains invalid characters
+	pub fn decode_secret(secret: &str) -> Result<Vec<u8>, &'static str> {
+		let mut bytes = Vec::with_capacity(secret.len() * 5 / 8);
+		let mut buffer: u64 = 0;
+		let mut bits = 0;
+
+		for c in secret.chars().filter(|c| *c != '=' && !c.is_whitespace()) {
+			let value = match c.to_ascii_uppercase() {
+				c @ 'A'..='Z' => c as u8 - b'A',
+				c @ '2'..='7' => c as u8 - b'2' + 26,
+				_ => return Err("should only contain base32 characters"),
+			};
+			buffer = (buffer << 5) | u64::from(value);
+			bits += 5;
+			if bits >= 8 {
+				bits -= 8;
+				#[allow(clippy::cast_possible_truncation)]
+				bytes.push((buffer >> bits) as u8);
+			}
+		}
+
+		Ok(bytes)
+	}
+
+	/// Generates a HMAC-SHA1 based one time password (RFC 4226)
+	///
+	/// # Errors
+	///
+	/// If the amount of digits isn't between 6 and 10, as RFC 4226 requires at
+	/// least 6 and the truncated hash only has 10 digits
+	///
+	/// # Panics
+	///
+	/// Never, as HMAC accepts keys of any length
+	pub fn hotp(
+		secret: &[u8], counter: u64, digits: u32,
+	) -> Result<String, &'static str> {
+		if !(6..=10).contains(&digits) {
+			return Err("digits should be between 6 and 10");
+		}
+
+		let mut mac = Hmac::<Sha1>::new_from_slice(secret)
+			.expect("HMAC to accept keys of any length");
+		mac.update(&counter.to_be_bytes());
+		let hash = mac.finalize().into_bytes();
+
+		let offset = (hash[hash.len() - 1] & 0xF) as usize;
+		let code = u32::from_be_bytes([
+			hash[offset] & 0x7F,
+			hash[offset + 1],
+			hash[offset + 2],
+			hash[offset + 3],
+		]);
+
+		Ok(format!(
+			"{:0width$}",
+			u64::from(code) % 10_u64.pow(digits),
+			width = digits as usize
+		))
+	}
+
+	/// Generates the current code for a base32 encoded secret
+	///
+	/// # Errors
+	///
+	/// If the secret isn't valid base32
+	pub fn now(secret: &str) -> Result<String, &'static str> {
+		let unix_time = time::OffsetDateTime::now_utc().unix_timestamp();
+		Ok(totp(&decode_secret(secret)?, unix_time.try_into().unwrap_or_default()))
+	}
+
+	#[must_use]
+	/// Generates a time based one time password (RFC 6238) for an UNIX time
+	///
+	/// # Panics
+	///
+	/// Never, as the default amount of digits is valid
+	pub fn totp(secret: &[u8], unix_time: u64) -> String {
+		hotp(secret, unix_time / PERIOD_SECONDS, DIGITS)
+			.expect("default digits to be valid")
+	}
+}
+
+#[cfg(all(test, feature = "totp_util"))]
+#[test]
+fn totp_generation() {
+	// Test vectors from RFC 6238, which use an ASCII secret
+	let secret = b"12345678901234567890";
+	for (unix_time, code) in [
+		(59, "94287082"),
+		(1_111_111_109, "07081804"),
+		(1_234_567_890, "89005924"),
+		(2_000_000_000, "69279037"),
+	] {
+		assert_eq!(
+			totp::hotp(secret, unix_time / totp::PERIOD_SECONDS, 8).unwrap(),
+			code
+		);
+	}
+	assert_eq!(totp::totp(secret, 59), "287082");
+	assert_eq!(totp::hotp(secret, 1, 10).unwrap().len(), 10);
+	assert!(totp::hotp(secret, 1, 5).is_err());
+	assert!(totp::hotp(secret, 1, 20).is_err());
+
+	let encoded = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+	assert_eq!(totp::decode_secret(encoded).unwrap(), secret);
+	assert_eq!(
+		totp::decode_secret(&encoded.to_lowercase()).unwrap(),
+		totp::decode_secret("GEZD GNBV GY3T QOJQ GEZD GNBV GY3T QOJQ").unwrap()
+	);
+	assert!(totp::decode_secret("not base32!").is_err());
+	assert_eq!(totp::now(encoded).unwrap().len(), 6);
+}
@@ -41,3 +41,68 @@ async fn get_messages() -> Result<(), ApiError> {
 
 	Ok(())
 }
+
+#[tokio::test]
+#[ignore]
+async fn get_user_status() -> Result<(), ApiError> {
+	let client = common::api_auth();
+
+	let user_id = common::USER_SESSION.user_id.clone();
+	let status_query = resonite::query::UserStatus::new(user_id.clone());
+	let status = dbg!(client.query(status_query).await?);
+
+	assert_eq!(status.user_id, user_id);
+
+	Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn group_members() -> Result<(), ApiError> {
+	let client = common::api_auth();
+
+	let group_id = resonite::id::Group::try_from("G-Resonite").unwrap();
+	let members_query = resonite::query::GroupMembers::new(group_id.clone());
+	let members = dbg!(client.query(members_query).await?);
+
+	let member = members.first().expect("group to have at least one member");
+	let member_query =
+		resonite::query::GroupMember::new(group_id.clone(), member.id.clone());
+	let member_details = dbg!(client.query(member_query).await?);
+	assert_eq!(&member_details, member);
+
+	let records_query = resonite::query::GroupRecords::new(group_id);
+	dbg!(client.query(records_query).await?);
+
+	Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn user_storage() -> Result<(), ApiError> {
+	let client = common::api_auth();
+
+	let user_id = common::USER_SESSION.user_id.clone();
+	let storage_query = resonite::query::Storage::new(user_id.clone());
+	let storage = dbg!(client.query(storage_query).await?);
+
+	assert_eq!(storage.owner_id, user_id.into());
+	assert!(storage.check_upload(0).fits());
+
+	Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn user_sessions() -> Result<(), ApiError> {
+	let client = common::api_auth();
+
+	let user_sessions = dbg!(client.query(resonite::query::UserSessions).await?);
+
+	assert!(user_sessions.iter().all(|user_session| {
+		user_session.user_id.as_ref() == Some(&common::USER_SESSION.user_id)
+			&& !user_session.is_expired()
+	}));
+
+	Ok(())
+}
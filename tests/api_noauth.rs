@@ -76,7 +76,8 @@ async fn search_users() -> Result<(), ApiError> {
 async fn sessions() -> Result<(), ApiError> {
 	let client = common::api_no_auth();
 
-	let sessions = dbg!(client.query(resonite::query::Sessions).await?);
+	let sessions =
+		dbg!(client.query(resonite::query::Sessions::default()).await?);
 
 	let public_session = sessions
 		.iter()
@@ -112,3 +113,52 @@ async fn get_group() -> Result<(), ApiError> {
 
 	Ok(())
 }
+
+#[tokio::test]
+#[ignore]
+async fn fetch_asset() -> Result<(), resonite::api_client::ApiError> {
+	let client = common::api_no_auth();
+
+	let user_query = resonite::query::UserInfo::new("Resonite");
+	let user = client.query(user_query).await?;
+	let icon_url = user
+		.profile
+		.and_then(|profile| profile.icon_url)
+		.expect("Resonite user to have a profile icon");
+
+	let cache_dir = std::env::temp_dir().join("resonite-asset-fetch-test");
+	let cache = resonite::api_client::AssetCache::new(&cache_dir, 10_000_000);
+	let fetcher = resonite::api_client::AssetFetcher::new(&client, Some(cache));
+
+	let downloaded = fetcher.fetch(&icon_url).await?;
+	assert!(!downloaded.is_empty());
+	let cached = fetcher.fetch(&icon_url).await?;
+	assert_eq!(downloaded, cached);
+
+	fetcher.cache().unwrap().clear().await?;
+
+	Ok(())
+}
+
+#[tokio::test]
+#[ignore]
+async fn asset_variants() -> Result<(), ApiError> {
+	let client = common::api_no_auth();
+
+	let user_query = resonite::query::UserInfo::new("Resonite");
+	let user = client.query(user_query).await?;
+	let icon_url = user
+		.profile
+		.and_then(|profile| profile.icon_url)
+		.expect("Resonite user to have a profile icon");
+
+	let variants_query = resonite::query::AssetVariants::new(icon_url.id());
+	let variants = dbg!(client.query(variants_query).await?);
+	assert!(!variants.is_empty());
+
+	let metadata_query = resonite::query::AssetMetadata::new(icon_url.id());
+	let metadata = dbg!(client.query(metadata_query).await?);
+	assert!(metadata.asset_identifier.eq_ignore_ascii_case(icon_url.id()));
+
+	Ok(())
+}
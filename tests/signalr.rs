@@ -9,11 +9,10 @@ mod common;
 async fn listen_signalr() -> Result<(), ApiError> {
 	let api_client = common::api_signalr().await;
 
-	let listener_ref = api_client.listen();
-	let mut listener_lock = listener_ref.lock().await;
+	let mut listener = api_client.listen();
 
 	for _i in 0..3 {
-		let next = listener_lock
+		let next = listener
 			.next()
 			.await
 			.expect("WS listener to have next item")